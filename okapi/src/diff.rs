@@ -0,0 +1,539 @@
+use crate::openapi3::{OpenApi, Operation, Parameter, ParameterValue, PathItem, RefOr};
+use schemars::schema::{InstanceType, SingleOrVec};
+use std::fmt;
+use std::fmt::Display;
+
+/// The differences between two `OpenApi` documents, created using [`OpenApi::diff`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OpenApiDiff {
+    /// All the changes, in the order of the paths and methods of the documents.
+    pub changes: Vec<Change>,
+}
+
+/// A single change between two `OpenApi` documents.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// The path was added.
+    AddedPath { path: String },
+    /// The path was removed.
+    RemovedPath { path: String },
+    /// The method was added to a path that already existed.
+    AddedOperation { path: String, method: String },
+    /// The method was removed from a path that still exists.
+    RemovedOperation { path: String, method: String },
+    /// A parameter was added to the operation.
+    AddedParameter {
+        path: String,
+        method: String,
+        name: String,
+        location: String,
+        required: bool,
+    },
+    /// A parameter was removed from the operation.
+    RemovedParameter {
+        path: String,
+        method: String,
+        name: String,
+        location: String,
+    },
+    /// A parameter became required or optional.
+    ChangedParameterRequired {
+        path: String,
+        method: String,
+        name: String,
+        location: String,
+        required: bool,
+    },
+    /// The `type` of the parameter schema changed.
+    ChangedParameterType {
+        path: String,
+        method: String,
+        name: String,
+        location: String,
+        old: Option<SingleOrVec<InstanceType>>,
+        new: Option<SingleOrVec<InstanceType>>,
+    },
+    /// A response status code was added to the operation.
+    AddedResponse {
+        path: String,
+        method: String,
+        status: String,
+    },
+    /// A response status code was removed from the operation.
+    RemovedResponse {
+        path: String,
+        method: String,
+        status: String,
+    },
+}
+
+impl Change {
+    /// Check if the change can break existing clients.
+    /// This is conservative: removing paths, operations and responses, adding required parameters,
+    /// making parameters required and changing the type of a parameter are all breaking.
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            Change::RemovedPath { .. }
+            | Change::RemovedOperation { .. }
+            | Change::ChangedParameterType { .. }
+            | Change::RemovedResponse { .. } => true,
+            Change::AddedParameter { required, .. }
+            | Change::ChangedParameterRequired { required, .. } => *required,
+            Change::AddedPath { .. }
+            | Change::AddedOperation { .. }
+            | Change::RemovedParameter { .. }
+            | Change::AddedResponse { .. } => false,
+        }
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::AddedPath { path } => write!(f, "Added path `{}`.", path),
+            Change::RemovedPath { path } => write!(f, "Removed path `{}`.", path),
+            Change::AddedOperation { path, method } => {
+                write!(f, "Added operation `{} {}`.", method, path)
+            }
+            Change::RemovedOperation { path, method } => {
+                write!(f, "Removed operation `{} {}`.", method, path)
+            }
+            Change::AddedParameter {
+                path,
+                method,
+                name,
+                location,
+                required,
+            } => write!(
+                f,
+                "Added {} {} parameter `{}` to `{} {}`.",
+                if *required { "required" } else { "optional" },
+                location,
+                name,
+                method,
+                path
+            ),
+            Change::RemovedParameter {
+                path,
+                method,
+                name,
+                location,
+            } => write!(
+                f,
+                "Removed {} parameter `{}` from `{} {}`.",
+                location, name, method, path
+            ),
+            Change::ChangedParameterRequired {
+                path,
+                method,
+                name,
+                location,
+                required,
+            } => write!(
+                f,
+                "The {} parameter `{}` of `{} {}` is now {}.",
+                location,
+                name,
+                method,
+                path,
+                if *required { "required" } else { "optional" }
+            ),
+            Change::ChangedParameterType {
+                path,
+                method,
+                name,
+                location,
+                old,
+                new,
+            } => write!(
+                f,
+                "The type of the {} parameter `{}` of `{} {}` changed from {} to {}.",
+                location,
+                name,
+                method,
+                path,
+                type_to_string(old),
+                type_to_string(new)
+            ),
+            Change::AddedResponse {
+                path,
+                method,
+                status,
+            } => write!(f, "Added response `{}` to `{} {}`.", status, method, path),
+            Change::RemovedResponse {
+                path,
+                method,
+                status,
+            } => write!(
+                f,
+                "Removed response `{}` from `{} {}`.",
+                status, method, path
+            ),
+        }
+    }
+}
+
+impl OpenApiDiff {
+    /// Check if any of the changes can break existing clients, see [`Change::is_breaking`].
+    pub fn has_breaking_changes(&self) -> bool {
+        self.changes.iter().any(Change::is_breaking)
+    }
+
+    /// Get only the changes that can break existing clients.
+    pub fn breaking_changes(&self) -> impl Iterator<Item = &Change> {
+        self.changes.iter().filter(|change| change.is_breaking())
+    }
+
+    /// Check if there are no changes at all.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl OpenApi {
+    /// Compare two documents and report the changes to the paths, operations, parameters and
+    /// response status codes. Schemas are only compared by their `type` for parameters.
+    ///
+    /// Parameter references are resolved using the `components` of their own document.
+    pub fn diff(old: &OpenApi, new: &OpenApi) -> OpenApiDiff {
+        let mut changes = Vec::new();
+        for (path, old_item) in &old.paths {
+            match new.paths.get(path) {
+                Some(new_item) => {
+                    diff_path_item(path, (old, old_item), (new, new_item), &mut changes)
+                }
+                None => changes.push(Change::RemovedPath { path: path.clone() }),
+            }
+        }
+        for path in new.paths.keys() {
+            if !old.paths.contains_key(path) {
+                changes.push(Change::AddedPath { path: path.clone() });
+            }
+        }
+        OpenApiDiff { changes }
+    }
+}
+
+fn diff_path_item(
+    path: &str,
+    (old_spec, old_item): (&OpenApi, &PathItem),
+    (new_spec, new_item): (&OpenApi, &PathItem),
+    changes: &mut Vec<Change>,
+) {
+    let old_operations = operations(old_item);
+    let new_operations = operations(new_item);
+    for (method, old_operation) in &old_operations {
+        let new_operation = new_operations
+            .iter()
+            .find(|(new_method, _)| new_method == method);
+        match new_operation {
+            Some((_, new_operation)) => {
+                let old_parameters = parameters(old_spec, old_item, old_operation);
+                let new_parameters = parameters(new_spec, new_item, new_operation);
+                diff_parameters(path, method, &old_parameters, &new_parameters, changes);
+                diff_responses(path, method, old_operation, new_operation, changes);
+            }
+            None => changes.push(Change::RemovedOperation {
+                path: path.to_owned(),
+                method: (*method).to_owned(),
+            }),
+        }
+    }
+    for (method, _) in &new_operations {
+        if !old_operations
+            .iter()
+            .any(|(old_method, _)| old_method == method)
+        {
+            changes.push(Change::AddedOperation {
+                path: path.to_owned(),
+                method: (*method).to_owned(),
+            });
+        }
+    }
+}
+
+fn diff_parameters(
+    path: &str,
+    method: &str,
+    old: &[&Parameter],
+    new: &[&Parameter],
+    changes: &mut Vec<Change>,
+) {
+    for old_parameter in old {
+        let name = old_parameter.name.clone();
+        let location = old_parameter.location.clone();
+        let new_parameter = match find_parameter(new, old_parameter) {
+            Some(new_parameter) => new_parameter,
+            None => {
+                changes.push(Change::RemovedParameter {
+                    path: path.to_owned(),
+                    method: method.to_owned(),
+                    name,
+                    location,
+                });
+                continue;
+            }
+        };
+        if old_parameter.required != new_parameter.required {
+            changes.push(Change::ChangedParameterRequired {
+                path: path.to_owned(),
+                method: method.to_owned(),
+                name: name.clone(),
+                location: location.clone(),
+                required: new_parameter.required,
+            });
+        }
+        let old_type = parameter_type(old_parameter);
+        let new_type = parameter_type(new_parameter);
+        if old_type != new_type {
+            changes.push(Change::ChangedParameterType {
+                path: path.to_owned(),
+                method: method.to_owned(),
+                name,
+                location,
+                old: old_type,
+                new: new_type,
+            });
+        }
+    }
+    for new_parameter in new {
+        if find_parameter(old, new_parameter).is_none() {
+            changes.push(Change::AddedParameter {
+                path: path.to_owned(),
+                method: method.to_owned(),
+                name: new_parameter.name.clone(),
+                location: new_parameter.location.clone(),
+                required: new_parameter.required,
+            });
+        }
+    }
+}
+
+fn find_parameter<'a>(list: &[&'a Parameter], parameter: &Parameter) -> Option<&'a Parameter> {
+    list.iter()
+        .find(|p| p.name == parameter.name && p.location == parameter.location)
+        .copied()
+}
+
+fn diff_responses(
+    path: &str,
+    method: &str,
+    old: &Operation,
+    new: &Operation,
+    changes: &mut Vec<Change>,
+) {
+    let old_statuses = statuses(old);
+    let new_statuses = statuses(new);
+    for status in &old_statuses {
+        if !new_statuses.contains(status) {
+            changes.push(Change::RemovedResponse {
+                path: path.to_owned(),
+                method: method.to_owned(),
+                status: status.clone(),
+            });
+        }
+    }
+    for status in new_statuses {
+        if !old_statuses.contains(&status) {
+            changes.push(Change::AddedResponse {
+                path: path.to_owned(),
+                method: method.to_owned(),
+                status,
+            });
+        }
+    }
+}
+
+/// Get all the operations of the `PathItem` together with their (lowercase) method.
+pub(crate) fn operations(path_item: &PathItem) -> Vec<(&'static str, &Operation)> {
+    let operations = [
+        ("get", &path_item.get),
+        ("put", &path_item.put),
+        ("post", &path_item.post),
+        ("delete", &path_item.delete),
+        ("options", &path_item.options),
+        ("head", &path_item.head),
+        ("patch", &path_item.patch),
+        ("trace", &path_item.trace),
+    ];
+    operations
+        .iter()
+        .filter_map(|(method, operation)| operation.as_ref().map(|op| (*method, op)))
+        .collect()
+}
+
+/// Get the parameters of the operation, including the ones of the `PathItem` it is part of.
+/// Parameters of the operation override the ones of the `PathItem`.
+pub(crate) fn parameters<'a>(
+    spec: &'a OpenApi,
+    path_item: &'a PathItem,
+    operation: &'a Operation,
+) -> Vec<&'a Parameter> {
+    let mut result: Vec<&Parameter> = Vec::new();
+    let all = operation.parameters.iter().chain(&path_item.parameters);
+    for parameter in all.filter_map(|parameter| resolve_parameter(spec, parameter)) {
+        let exists = result
+            .iter()
+            .any(|p| p.name == parameter.name && p.location == parameter.location);
+        if !exists {
+            result.push(parameter);
+        }
+    }
+    result
+}
+
+/// Resolve a parameter reference to `#/components/parameters/`.
+/// Returns `None` for references that can not be resolved.
+fn resolve_parameter<'a>(
+    spec: &'a OpenApi,
+    parameter: &'a RefOr<Parameter>,
+) -> Option<&'a Parameter> {
+    match parameter {
+        RefOr::Object(parameter) => Some(parameter),
+        RefOr::Ref(reference) => {
+            let name = reference
+                .reference
+                .strip_prefix("#/components/parameters/")?;
+            match spec.components.as_ref()?.parameters.get(name)? {
+                RefOr::Object(parameter) => Some(parameter),
+                RefOr::Ref(_) => None,
+            }
+        }
+    }
+}
+
+fn parameter_type(parameter: &Parameter) -> Option<SingleOrVec<InstanceType>> {
+    match &parameter.value {
+        ParameterValue::Schema { schema, .. } => schema.instance_type.clone(),
+        ParameterValue::Content { .. } => None,
+    }
+}
+
+/// Get the status codes of all the responses, `default` is included when it is set.
+fn statuses(operation: &Operation) -> Vec<String> {
+    let responses = &operation.responses;
+    let mut statuses: Vec<String> = responses.responses.keys().cloned().collect();
+    if responses.default.is_some() && !statuses.iter().any(|status| status == "default") {
+        statuses.push("default".to_owned());
+    }
+    statuses
+}
+
+fn type_to_string(instance_type: &Option<SingleOrVec<InstanceType>>) -> String {
+    match instance_type {
+        Some(instance_type) => {
+            serde_json::to_string(instance_type).expect("Could not serialize the type as JSON.")
+        }
+        None => "none".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Change;
+    use crate::openapi3::*;
+    use schemars::schema::{InstanceType, SchemaObject};
+
+    fn query_parameter(name: &str, required: bool) -> RefOr<Parameter> {
+        Parameter {
+            name: name.to_owned(),
+            location: "query".to_owned(),
+            description: None,
+            required,
+            deprecated: false,
+            allow_empty_value: false,
+            value: ParameterValue::Schema {
+                style: None,
+                explode: None,
+                allow_reserved: false,
+                schema: SchemaObject {
+                    instance_type: Some(InstanceType::String.into()),
+                    ..SchemaObject::default()
+                },
+                example: None,
+                examples: None,
+            },
+            extensions: Object::default(),
+        }
+        .into()
+    }
+
+    fn spec_with_get(path: &str, parameters: Vec<RefOr<Parameter>>) -> OpenApi {
+        let mut spec = OpenApi::new();
+        let mut responses = Responses::default();
+        responses
+            .responses
+            .insert("200".to_owned(), Response::default().into());
+        let operation = Operation {
+            parameters,
+            responses,
+            ..Operation::default()
+        };
+        spec.paths.insert(
+            path.to_owned(),
+            PathItem {
+                get: Some(operation),
+                ..PathItem::default()
+            },
+        );
+        spec
+    }
+
+    #[test]
+    fn test_added_endpoint_is_not_breaking() {
+        let old = spec_with_get("/users", vec![]);
+        let mut new = old.clone();
+        new.paths.extend(spec_with_get("/posts", vec![]).paths);
+        let diff = OpenApi::diff(&old, &new);
+        assert_eq!(
+            diff.changes,
+            vec![Change::AddedPath {
+                path: "/posts".to_owned()
+            }]
+        );
+        assert!(!diff.has_breaking_changes());
+        // The other way around the path is removed.
+        assert!(OpenApi::diff(&new, &old).has_breaking_changes());
+    }
+
+    #[test]
+    fn test_required_parameter_is_breaking() {
+        let old = spec_with_get("/users", vec![query_parameter("page", false)]);
+        let new = spec_with_get("/users", vec![query_parameter("page", true)]);
+        let diff = OpenApi::diff(&old, &new);
+        assert_eq!(
+            diff.changes,
+            vec![Change::ChangedParameterRequired {
+                path: "/users".to_owned(),
+                method: "get".to_owned(),
+                name: "page".to_owned(),
+                location: "query".to_owned(),
+                required: true,
+            }]
+        );
+        assert!(diff.has_breaking_changes());
+        assert!(!OpenApi::diff(&new, &old).has_breaking_changes());
+
+        let new = spec_with_get(
+            "/users",
+            vec![query_parameter("page", false), query_parameter("id", true)],
+        );
+        assert!(OpenApi::diff(&old, &new).has_breaking_changes());
+    }
+
+    #[test]
+    fn test_removed_response_is_breaking() {
+        let old = spec_with_get("/users", vec![]);
+        let mut new = old.clone();
+        new.paths
+            .get_mut("/users")
+            .unwrap()
+            .get
+            .as_mut()
+            .unwrap()
+            .responses = Responses::default();
+        let diff = OpenApi::diff(&old, &new);
+        assert_eq!(diff.breaking_changes().count(), 1);
+        assert!(OpenApi::diff(&old, &old).is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+use crate::diff::operations;
+use crate::openapi3::OpenApi;
+
+/// A short description of an operation in an `OpenApi` document, created using
+/// [`OpenApi::operation_index`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct OperationRef {
+    /// The path of the operation, like `/users/{id}`.
+    pub path: String,
+    /// The lowercase HTTP method of the operation, like `get`.
+    pub method: String,
+    /// The `operationId` of the operation.
+    pub operation_id: Option<String>,
+    /// The `summary` of the operation.
+    pub summary: Option<String>,
+    /// The `tags` of the operation.
+    pub tags: Vec<String>,
+}
+
+impl OpenApi {
+    /// List all the operations of the document, for example to create an index page.
+    /// The operations are sorted by path, and then by method in the order of the fields of
+    /// `PathItem` (`get`, `put`, `post`, `delete`, `options`, `head`, `patch`, `trace`).
+    #[must_use]
+    pub fn operation_index(&self) -> Vec<OperationRef> {
+        let mut paths: Vec<_> = self.paths.iter().collect();
+        paths.sort_by_key(|(path, _)| *path);
+        paths
+            .into_iter()
+            .flat_map(|(path, path_item)| {
+                operations(path_item)
+                    .into_iter()
+                    .map(move |(method, operation)| OperationRef {
+                        path: path.clone(),
+                        method: method.to_owned(),
+                        operation_id: operation.operation_id.clone(),
+                        summary: operation.summary.clone(),
+                        tags: operation.tags.clone(),
+                    })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::openapi3::{Operation, PathItem};
+
+    fn operation(operation_id: &str, tags: &[&str]) -> Option<Operation> {
+        Some(Operation {
+            operation_id: Some(operation_id.to_owned()),
+            summary: Some(format!("Summary of {}", operation_id)),
+            tags: tags.iter().map(|tag| (*tag).to_owned()).collect(),
+            ..Operation::default()
+        })
+    }
+
+    #[test]
+    fn test_operation_index() {
+        let mut spec = OpenApi::new();
+        spec.paths.insert(
+            "/users".to_owned(),
+            PathItem {
+                post: operation("create_user", &["users"]),
+                get: operation("list_users", &["users"]),
+                ..PathItem::default()
+            },
+        );
+        spec.paths.insert(
+            "/pets".to_owned(),
+            PathItem {
+                delete: operation("delete_pets", &[]),
+                ..PathItem::default()
+            },
+        );
+        let index = spec.operation_index();
+        let entries: Vec<_> = index
+            .iter()
+            .map(|entry| {
+                (
+                    entry.path.as_str(),
+                    entry.method.as_str(),
+                    entry.operation_id.as_deref().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            entries,
+            vec![
+                ("/pets", "delete", "delete_pets"),
+                ("/users", "get", "list_users"),
+                ("/users", "post", "create_user"),
+            ]
+        );
+        assert_eq!(
+            index[2],
+            OperationRef {
+                path: "/users".to_owned(),
+                method: "post".to_owned(),
+                operation_id: Some("create_user".to_owned()),
+                summary: Some("Summary of create_user".to_owned()),
+                tags: vec!["users".to_owned()],
+            }
+        );
+    }
+}
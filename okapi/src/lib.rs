@@ -4,8 +4,13 @@
 pub type Map<K, V> = schemars::Map<K, V>;
 pub type MapEntry<'a, K, V> = schemars::MapEntry<'a, K, V>;
 
+pub mod diff;
+pub mod index;
 pub mod merge;
 pub mod openapi3;
+pub mod patch;
+pub mod postman;
+pub mod validate;
 
 /// Re-export the current version of `Schemars` used by `Okapi`.
 pub use schemars;
@@ -28,6 +28,56 @@ impl OpenApi {
     pub fn merge_spec<S: Display>(mut self, path_prefix: &S, s2: &Self) -> Result<(), MergeError> {
         merge_specs(&mut self, path_prefix, s2)
     }
+
+    /// Merge the given OpenAPI spec into the current one.
+    ///
+    /// Unlike [`merge_specs`] this returns an error when both specs contain an operation for
+    /// the same path and method, or a schema with the same name but a different definition.
+    /// Identical schemas are merged into one. When an error is returned `self` is not changed.
+    pub fn merge(&mut self, other: OpenApi) -> Result<(), MergeError> {
+        for (path, path_item) in &other.paths {
+            if let Some(current) = self.paths.get(path) {
+                check_path_item_conflicts(path, current, path_item)?;
+            }
+        }
+        if let (Some(c1), Some(c2)) = (&self.components, &other.components) {
+            for (name, schema) in &c2.schemas {
+                match c1.schemas.get(name) {
+                    Some(current) if current != schema => {
+                        return Err(MergeError::new(format!(
+                            "Both specs contain a different schema named `{}`.",
+                            name
+                        )));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        merge_specs(self, &"", &other)
+    }
+}
+
+/// Returns an error if both `PathItem`s contain an operation for the same method.
+fn check_path_item_conflicts(path: &str, s1: &PathItem, s2: &PathItem) -> Result<(), MergeError> {
+    let operations = [
+        ("GET", &s1.get, &s2.get),
+        ("PUT", &s1.put, &s2.put),
+        ("POST", &s1.post, &s2.post),
+        ("DELETE", &s1.delete, &s2.delete),
+        ("OPTIONS", &s1.options, &s2.options),
+        ("HEAD", &s1.head, &s2.head),
+        ("PATCH", &s1.patch, &s2.patch),
+        ("TRACE", &s1.trace, &s2.trace),
+    ];
+    for (method, op1, op2) in operations {
+        if op1.is_some() && op2.is_some() {
+            return Err(MergeError::new(format!(
+                "Both specs contain an operation for `{} {}`.",
+                method, path
+            )));
+        }
+    }
+    Ok(())
 }
 
 /// Marge the list of all specs together into on big OpenApi object.
@@ -59,6 +109,7 @@ pub fn merge_specs<S: Display>(
     merge_tags(&mut s1.tags, &s2.tags)?;
     // Replace the external_docs info as 1 block, so don't mix
     merge_option(&mut s1.external_docs, &s2.external_docs);
+    merge_map(&mut s1.webhooks, &s2.webhooks, "webhooks");
     merge_map(&mut s1.extensions, &s2.extensions, "extensions");
     Ok(())
 }
@@ -294,3 +345,71 @@ pub fn merge_vec<T: Clone>(s1: &mut Vec<T>, s2: &[T]) {
         s1.push(value.clone());
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::openapi3::{Operation, SchemaObject};
+
+    fn spec_with(path: &str, path_item: PathItem, schema: Option<(&str, SchemaObject)>) -> OpenApi {
+        let mut spec = OpenApi::new();
+        spec.paths.insert(path.to_owned(), path_item);
+        let mut components = Components::default();
+        if let Some((name, schema)) = schema {
+            components.schemas.insert(name.to_owned(), schema);
+        }
+        spec.components = Some(components);
+        spec
+    }
+
+    fn get_item() -> PathItem {
+        PathItem {
+            get: Some(Operation::default()),
+            ..PathItem::default()
+        }
+    }
+
+    fn post_item() -> PathItem {
+        PathItem {
+            post: Some(Operation::default()),
+            ..PathItem::default()
+        }
+    }
+
+    fn schema(format: &str) -> SchemaObject {
+        SchemaObject {
+            format: Some(format.to_owned()),
+            ..SchemaObject::default()
+        }
+    }
+
+    #[test]
+    fn test_merge_different_methods() {
+        let mut spec = spec_with("/user", get_item(), Some(("User", schema("a"))));
+        let other = spec_with("/user", post_item(), Some(("User", schema("a"))));
+        spec.merge(other).unwrap();
+        let path_item = &spec.paths["/user"];
+        assert!(path_item.get.is_some() && path_item.post.is_some());
+        assert_eq!(spec.components.unwrap().schemas.len(), 1);
+    }
+
+    #[test]
+    fn test_merge_same_method() {
+        let mut spec = spec_with("/user", get_item(), None);
+        let other = spec_with("/user", get_item(), None);
+        let err = spec.merge(other).unwrap_err();
+        assert_eq!(err.msg, "Both specs contain an operation for `GET /user`.");
+    }
+
+    #[test]
+    fn test_merge_different_schema() {
+        let mut spec = spec_with("/user", get_item(), Some(("User", schema("a"))));
+        let other = spec_with("/admin", get_item(), Some(("User", schema("b"))));
+        let err = spec.merge(other).unwrap_err();
+        assert_eq!(
+            err.msg,
+            "Both specs contain a different schema named `User`."
+        );
+        assert!(!spec.paths.contains_key("/admin"));
+    }
+}
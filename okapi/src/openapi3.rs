@@ -2,7 +2,8 @@ use crate::Map;
 pub use schemars::schema::SchemaObject;
 #[cfg(feature = "impl_json_schema")]
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 
 pub type Object = Map<String, Value>;
@@ -40,6 +41,76 @@ impl OpenApi {
     pub fn default_version() -> String {
         "3.0.0".to_owned()
     }
+
+    /// Serialize the document as JSON, indented when `pretty` is set and on a single line
+    /// otherwise. The keys are written in the same order as the `Map`s, so the output is stable
+    /// between runs.
+    pub fn to_json(&self, pretty: bool) -> Result<String, serde_json::Error> {
+        if pretty {
+            serde_json::to_string_pretty(self)
+        } else {
+            serde_json::to_string(self)
+        }
+    }
+
+    /// Deserialize a document from JSON. Fields that are not part of the model, like `x-`
+    /// prefixed extensions, are kept in the `extensions` of the objects, so they are written
+    /// again by [`to_json`](Self::to_json).
+    pub fn from_json(s: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(s)
+    }
+
+    /// Create a copy of the document that only contains the paths and methods of the operations,
+    /// with their `operationId` and `summary`. All `components`, parameters, request bodies and
+    /// responses are removed, so the document has no `$ref`s left. This is useful for tools that
+    /// only need the list of endpoints.
+    #[must_use]
+    pub fn strip_components(&self) -> OpenApi {
+        let strip = |operation: &Option<Operation>| {
+            operation.as_ref().map(|operation| Operation {
+                operation_id: operation.operation_id.clone(),
+                summary: operation.summary.clone(),
+                ..Operation::default()
+            })
+        };
+        OpenApi {
+            openapi: self.openapi.clone(),
+            info: self.info.clone(),
+            servers: self.servers.clone(),
+            paths: self
+                .paths
+                .iter()
+                .map(|(path, item)| {
+                    let item = PathItem {
+                        get: strip(&item.get),
+                        put: strip(&item.put),
+                        post: strip(&item.post),
+                        delete: strip(&item.delete),
+                        options: strip(&item.options),
+                        head: strip(&item.head),
+                        patch: strip(&item.patch),
+                        trace: strip(&item.trace),
+                        ..PathItem::default()
+                    };
+                    (path.clone(), item)
+                })
+                .collect(),
+            ..OpenApi::default()
+        }
+    }
+
+    /// Serialize the document as YAML.
+    /// The keys are written in the same order as the `Map`s, so the output is stable between runs.
+    #[cfg(feature = "yaml")]
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(self)
+    }
+
+    /// Deserialize a document from YAML.
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml(s: &str) -> Result<Self, serde_yaml::Error> {
+        serde_yaml::from_str(s)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
@@ -59,6 +130,9 @@ pub struct OpenApi {
     pub tags: Vec<Tag>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub external_docs: Option<ExternalDocs>,
+    /// Incoming requests that the API may send to the client, only part of OpenAPI 3.1.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub webhooks: Map<String, PathItem>,
     #[serde(flatten)]
     pub extensions: Object,
 }
@@ -204,9 +278,9 @@ pub struct Operation {
 pub struct Responses {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default: Option<RefOr<Response>>,
-    #[serde(flatten)]
+    #[serde(flatten, deserialize_with = "deserialize_non_extensions")]
     pub responses: Map<String, RefOr<Response>>,
-    #[serde(flatten)]
+    #[serde(flatten, deserialize_with = "deserialize_extensions")]
     pub extensions: Object,
 }
 
@@ -279,6 +353,7 @@ pub struct Parameter {
 #[serde(untagged, rename_all = "camelCase")]
 #[allow(clippy::large_enum_variant)] // Removing this requires breaking changes to API.
 pub enum ParameterValue {
+    #[serde(rename_all = "camelCase")]
     Schema {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         style: Option<ParameterStyle>,
@@ -467,9 +542,9 @@ pub struct Link {
 #[cfg_attr(feature = "impl_json_schema", derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct Callback {
-    #[serde(flatten)]
+    #[serde(flatten, deserialize_with = "deserialize_non_extensions")]
     pub callbacks: Map<String, PathItem>,
-    #[serde(flatten)]
+    #[serde(flatten, deserialize_with = "deserialize_extensions")]
     pub extensions: Object,
 }
 
@@ -489,6 +564,21 @@ pub struct MediaType {
     pub extensions: Object,
 }
 
+/// Used in a schema with `oneOf`/`anyOf` to tell which property selects the schema.
+/// Schemas can not have this field directly, it needs to be added to the `extensions` of the
+/// `SchemaObject` as `discriminator`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+#[cfg_attr(feature = "impl_json_schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct Discriminator {
+    pub property_name: String,
+    /// Map of property values to schema names or references.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub mapping: Map<String, String>,
+    #[serde(flatten)]
+    pub extensions: Object,
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
 #[cfg_attr(feature = "impl_json_schema", derive(JsonSchema))]
 #[serde(rename_all = "camelCase")]
@@ -534,3 +624,198 @@ pub struct Encoding {
 fn is_false(b: impl std::borrow::Borrow<bool>) -> bool {
     !b.borrow()
 }
+
+/// Only keep the `x-` prefixed keys, used when a struct flattens more then one map.
+fn deserialize_extensions<'de, D>(deserializer: D) -> Result<Object, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let map = Object::deserialize(deserializer)?;
+    Ok(map
+        .into_iter()
+        .filter(|(key, _)| key.starts_with("x-"))
+        .collect())
+}
+
+/// Skip all the `x-` prefixed keys, used when a struct flattens more then one map.
+fn deserialize_non_extensions<'de, D, T>(deserializer: D) -> Result<Map<String, T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let map = Object::deserialize(deserializer)?;
+    map.into_iter()
+        .filter(|(key, _)| !key.starts_with("x-"))
+        .map(|(key, value)| {
+            let value = serde_json::from_value(value).map_err(D::Error::custom)?;
+            Ok((key, value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn example_spec() -> OpenApi {
+        let mut spec = OpenApi::new();
+        spec.info.title = "Example".to_owned();
+        spec.info.version = "1.0.0".to_owned();
+        let mut responses = Responses::default();
+        responses.responses.insert(
+            "200".to_owned(),
+            Response {
+                description: "Success".to_owned(),
+                ..Response::default()
+            }
+            .into(),
+        );
+        spec.paths.insert(
+            "/user".to_owned(),
+            PathItem {
+                get: Some(Operation {
+                    operation_id: Some("get_user".to_owned()),
+                    responses,
+                    ..Operation::default()
+                }),
+                ..PathItem::default()
+            },
+        );
+        spec
+    }
+
+    #[test]
+    fn test_to_json() {
+        let spec = example_spec();
+        let pretty = spec.to_json(true).unwrap();
+        let compact = spec.to_json(false).unwrap();
+        assert!(pretty.contains('\n'));
+        assert!(!compact.contains('\n'));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&pretty).unwrap(),
+            serde_json::from_str::<serde_json::Value>(&compact).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip_keeps_extensions() {
+        let json = serde_json::json!({
+            "openapi": "3.0.0",
+            "info": {"title": "Pets", "version": "1.0.0", "x-logo": {"url": "logo.png"}},
+            "x-custom": true,
+            "paths": {
+                "/pets": {
+                    "x-path": 1,
+                    "get": {
+                        "x-internal": "yes",
+                        "responses": {
+                            "200": {"description": "The pets.", "x-response": [1, 2]},
+                            "x-responses": "kept"
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "Pet": {"type": "object", "x-schema": {"nested": null}}
+                },
+                "x-components": "kept"
+            }
+        });
+        let spec = OpenApi::from_json(&json.to_string()).unwrap();
+        assert_eq!(spec.extensions["x-custom"], true);
+        assert!(!spec.paths["/pets"]
+            .get
+            .as_ref()
+            .unwrap()
+            .responses
+            .responses
+            .contains_key("x-responses"));
+        let output: serde_json::Value =
+            serde_json::from_str(&spec.to_json(false).unwrap()).unwrap();
+        assert_eq!(output, json);
+    }
+
+    /// Deserialize the `json` as `T` and check that it is serialized to the same JSON again.
+    fn assert_round_trip<T: Serialize + DeserializeOwned>(json: serde_json::Value) -> T {
+        let value: T = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&value).unwrap(), json);
+        value
+    }
+
+    #[test]
+    fn test_extensions_round_trip() {
+        let spec: OpenApi = assert_round_trip(serde_json::json!({
+            "openapi": "3.0.0",
+            "info": {"title": "Pets", "version": "1.0.0"},
+            "paths": {},
+            "x-foo": "bar"
+        }));
+        assert_eq!(spec.extensions["x-foo"], "bar");
+        let path_item: PathItem = assert_round_trip(serde_json::json!({
+            "summary": "Pets",
+            "x-foo": "bar"
+        }));
+        assert_eq!(path_item.extensions["x-foo"], "bar");
+        let operation: Operation = assert_round_trip(serde_json::json!({
+            "operationId": "list_pets",
+            "responses": {},
+            "x-foo": "bar"
+        }));
+        assert_eq!(operation.extensions["x-foo"], "bar");
+        let response: Response = assert_round_trip(serde_json::json!({
+            "description": "The pets.",
+            "x-foo": "bar"
+        }));
+        assert_eq!(response.extensions["x-foo"], "bar");
+        let parameter: Parameter = assert_round_trip(serde_json::json!({
+            "name": "limit",
+            "in": "query",
+            "schema": {"type": "integer"},
+            "x-foo": "bar"
+        }));
+        assert_eq!(parameter.extensions["x-foo"], "bar");
+        let schema: SchemaObject = assert_round_trip(serde_json::json!({
+            "type": "object",
+            "x-foo": "bar"
+        }));
+        assert_eq!(schema.extensions["x-foo"], "bar");
+    }
+
+    #[test]
+    fn test_strip_components() {
+        let mut spec = example_spec();
+        let mut components = Components::default();
+        components
+            .schemas
+            .insert("User".to_owned(), SchemaObject::default());
+        spec.components = Some(components);
+        let user = spec.paths.get_mut("/user").unwrap();
+        let get = user.get.as_mut().unwrap();
+        get.summary = Some("Get a user".to_owned());
+        get.description = Some("Get the user with the id.".to_owned());
+        user.post = Some(Operation::default());
+        spec.paths.insert("/pet".to_owned(), PathItem::default());
+
+        let stripped = spec.strip_components();
+        assert!(stripped.components.is_none());
+        assert_eq!(stripped.info, spec.info);
+        assert_eq!(stripped.paths.len(), 2);
+        let user = &stripped.paths["/user"];
+        assert!(user.post.is_some());
+        let get = user.get.as_ref().unwrap();
+        assert_eq!(get.operation_id.as_deref(), Some("get_user"));
+        assert_eq!(get.summary.as_deref(), Some("Get a user"));
+        assert!(get.description.is_none());
+        assert!(get.responses.responses.is_empty());
+        assert!(!stripped.to_json(false).unwrap().contains("$ref"));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_yaml_round_trip() {
+        let spec = example_spec();
+        let yaml = spec.to_yaml().unwrap();
+        assert_eq!(OpenApi::from_yaml(&yaml).unwrap(), spec);
+    }
+}
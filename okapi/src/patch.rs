@@ -0,0 +1,330 @@
+use crate::openapi3::OpenApi;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
+pub struct PatchError {
+    pub msg: String,
+}
+
+impl Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+
+impl PatchError {
+    fn new<S: AsRef<str>>(msg: S) -> Self {
+        PatchError {
+            msg: msg.as_ref().to_owned(),
+        }
+    }
+}
+
+/// A single operation of a JSON Patch document, see
+/// [RFC 6902](https://datatracker.ietf.org/doc/html/rfc6902).
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value },
+}
+
+impl OpenApi {
+    /// Apply a JSON Patch ([RFC 6902](https://datatracker.ietf.org/doc/html/rfc6902)) to the
+    /// serialized document, for example to change fields that can not be set from code.
+    ///
+    /// The `patch` should be an array of operations like
+    /// `{"op": "replace", "path": "/info/version", "value": "2.0.0"}`.
+    /// Returns an error when a pointer is invalid, a `test` operation fails or the patched
+    /// document is not a valid `OpenApi` document. When an error is returned `self` is not changed.
+    pub fn apply_patch(&mut self, patch: &Value) -> Result<(), PatchError> {
+        let operations: Vec<PatchOperation> = serde_json::from_value(patch.clone())
+            .map_err(|err| PatchError::new(format!("Invalid JSON Patch: {}", err)))?;
+        let mut document = serde_json::to_value(&*self)
+            .map_err(|err| PatchError::new(format!("Could not serialize document: {}", err)))?;
+        for operation in operations {
+            apply_operation(&mut document, operation)?;
+        }
+        *self = serde_json::from_value(document).map_err(|err| {
+            PatchError::new(format!("Patched document is not a valid document: {}", err))
+        })?;
+        Ok(())
+    }
+
+    /// Recursively merge the `overlay` into the serialized document, for example to add
+    /// hand written descriptions to a generated document.
+    ///
+    /// Objects are merged key by key and missing objects are created, arrays and other values
+    /// in the overlay replace the current value. Returns an error when the merged document is
+    /// not a valid `OpenApi` document, in that case `self` is not changed.
+    pub fn apply_overlay(&mut self, overlay: &Value) -> Result<(), PatchError> {
+        let mut document = serde_json::to_value(&*self)
+            .map_err(|err| PatchError::new(format!("Could not serialize document: {}", err)))?;
+        merge_value(&mut document, overlay);
+        *self = serde_json::from_value(document).map_err(|err| {
+            PatchError::new(format!("Merged document is not a valid document: {}", err))
+        })?;
+        Ok(())
+    }
+}
+
+/// Merge `overlay` into `target`, only objects are merged, other values are replaced.
+fn merge_value(target: &mut Value, overlay: &Value) {
+    match (target, overlay) {
+        (Value::Object(target), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                merge_value(
+                    target
+                        .entry(key.clone())
+                        .or_insert_with(|| Value::Object(Default::default())),
+                    value,
+                );
+            }
+        }
+        (target, overlay) => *target = overlay.clone(),
+    }
+}
+
+fn apply_operation(document: &mut Value, operation: PatchOperation) -> Result<(), PatchError> {
+    match operation {
+        PatchOperation::Add { path, value } => add(document, &path, value),
+        PatchOperation::Remove { path } => remove(document, &path).map(|_| ()),
+        PatchOperation::Replace { path, value } => {
+            *get_mut(document, &path)? = value;
+            Ok(())
+        }
+        PatchOperation::Move { from, path } => {
+            if path.starts_with(&format!("{}/", from)) {
+                return Err(PatchError::new(format!(
+                    "Can not move `{}` into one of its children `{}`.",
+                    from, path
+                )));
+            }
+            let value = remove(document, &from)?;
+            add(document, &path, value)
+        }
+        PatchOperation::Copy { from, path } => {
+            let value = get_mut(document, &from)?.clone();
+            add(document, &path, value)
+        }
+        PatchOperation::Test { path, value } => {
+            if *get_mut(document, &path)? == value {
+                Ok(())
+            } else {
+                Err(PatchError::new(format!(
+                    "Test failed, the value at `{}` is not `{}`.",
+                    path, value
+                )))
+            }
+        }
+    }
+}
+
+/// Split a JSON Pointer ([RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901)) into
+/// its unescaped reference tokens.
+fn parse_pointer(pointer: &str) -> Result<Vec<String>, PatchError> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    if !pointer.starts_with('/') {
+        return Err(PatchError::new(format!(
+            "Invalid JSON Pointer `{}`, it should start with `/`.",
+            pointer
+        )));
+    }
+    Ok(pointer[1..]
+        .split('/')
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect())
+}
+
+/// Parse an array index, `-` (the end of the array) is only allowed when `allow_end` is set.
+fn array_index(token: &str, len: usize, allow_end: bool) -> Option<usize> {
+    if allow_end && token == "-" {
+        return Some(len);
+    }
+    if token.is_empty() || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+    let index = token.parse::<usize>().ok()?;
+    if index < len || (allow_end && index == len) {
+        Some(index)
+    } else {
+        None
+    }
+}
+
+fn missing(pointer: &str) -> PatchError {
+    PatchError::new(format!("No value found at `{}`.", pointer))
+}
+
+fn get_tokens_mut<'a>(document: &'a mut Value, tokens: &[String]) -> Option<&'a mut Value> {
+    let mut current = document;
+    for token in tokens {
+        current = match current {
+            Value::Object(map) => map.get_mut(token)?,
+            Value::Array(list) => {
+                let index = array_index(token, list.len(), false)?;
+                list.get_mut(index)?
+            }
+            _ => return None,
+        };
+    }
+    Some(current)
+}
+
+fn get_mut<'a>(document: &'a mut Value, pointer: &str) -> Result<&'a mut Value, PatchError> {
+    let tokens = parse_pointer(pointer)?;
+    get_tokens_mut(document, &tokens).ok_or_else(|| missing(pointer))
+}
+
+fn add(document: &mut Value, pointer: &str, value: Value) -> Result<(), PatchError> {
+    let tokens = parse_pointer(pointer)?;
+    let (last, parent) = match tokens.split_last() {
+        Some(split) => split,
+        None => {
+            *document = value;
+            return Ok(());
+        }
+    };
+    match get_tokens_mut(document, parent) {
+        Some(Value::Object(map)) => {
+            map.insert(last.clone(), value);
+            Ok(())
+        }
+        Some(Value::Array(list)) => {
+            let index = array_index(last, list.len(), true).ok_or_else(|| missing(pointer))?;
+            list.insert(index, value);
+            Ok(())
+        }
+        _ => Err(missing(pointer)),
+    }
+}
+
+fn remove(document: &mut Value, pointer: &str) -> Result<Value, PatchError> {
+    let tokens = parse_pointer(pointer)?;
+    let (last, parent) = tokens
+        .split_last()
+        .ok_or_else(|| PatchError::new("Can not remove the whole document."))?;
+    match get_tokens_mut(document, parent) {
+        Some(Value::Object(map)) => map.remove(last).ok_or_else(|| missing(pointer)),
+        Some(Value::Array(list)) => {
+            let index = array_index(last, list.len(), false).ok_or_else(|| missing(pointer))?;
+            Ok(list.remove(index))
+        }
+        _ => Err(missing(pointer)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::openapi3::Tag;
+    use serde_json::json;
+
+    fn spec() -> OpenApi {
+        let mut spec = OpenApi::new();
+        spec.info.title = "Pets".to_owned();
+        spec.info.version = "1.0.0".to_owned();
+        spec
+    }
+
+    #[test]
+    fn test_replace_version() {
+        let mut spec = spec();
+        spec.apply_patch(&json!([
+            {"op": "test", "path": "/info/version", "value": "1.0.0"},
+            {"op": "replace", "path": "/info/version", "value": "2.0.0"}
+        ]))
+        .unwrap();
+        assert_eq!(spec.info.version, "2.0.0");
+    }
+
+    #[test]
+    fn test_add_move_copy_remove() {
+        let mut spec = spec();
+        spec.apply_patch(&json!([
+            {"op": "add", "path": "/tags", "value": []},
+            {"op": "add", "path": "/tags/-", "value": {"name": "cats"}},
+            {"op": "add", "path": "/tags/0", "value": {"name": "dogs"}},
+            {"op": "copy", "from": "/info/title", "path": "/tags/1/description"},
+            {"op": "move", "from": "/tags/0", "path": "/tags/-"},
+            {"op": "remove", "path": "/tags/1/name"},
+            {"op": "add", "path": "/tags/1/name", "value": "birds"}
+        ]))
+        .unwrap();
+        let names: Vec<_> = spec
+            .tags
+            .iter()
+            .map(|tag: &Tag| tag.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["cats", "birds"]);
+        assert_eq!(spec.tags[0].description.as_deref(), Some("Pets"));
+    }
+
+    #[test]
+    fn test_overlay() {
+        let mut spec: OpenApi = serde_json::from_value(json!({
+            "openapi": "3.0.0",
+            "info": {"title": "Pets", "version": "1.0.0"},
+            "paths": {
+                "/pets": {
+                    "get": {
+                        "operationId": "list_pets",
+                        "tags": ["pets"],
+                        "responses": {"200": {"description": "The pets."}}
+                    }
+                }
+            }
+        }))
+        .unwrap();
+        spec.apply_overlay(&json!({
+            "info": {"description": "All about pets."},
+            "paths": {
+                "/pets": {"get": {"description": "List all the pets.", "tags": ["animals"]}},
+                "/owners": {"get": {"responses": {"200": {"description": "The owners."}}}}
+            }
+        }))
+        .unwrap();
+        assert_eq!(spec.info.title, "Pets");
+        assert_eq!(spec.info.description.as_deref(), Some("All about pets."));
+        let get = spec.paths["/pets"].get.as_ref().unwrap();
+        assert_eq!(get.description.as_deref(), Some("List all the pets."));
+        assert_eq!(get.operation_id.as_deref(), Some("list_pets"));
+        assert_eq!(get.tags, vec!["animals"]);
+        assert!(get.responses.responses.contains_key("200"));
+        assert!(spec.paths["/owners"].get.is_some());
+        let invalid = spec.apply_overlay(&json!({"info": {"title": 1}}));
+        assert!(invalid.is_err());
+        assert_eq!(spec.info.title, "Pets");
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut spec = spec();
+        let failed_test = spec.apply_patch(&json!([
+            {"op": "replace", "path": "/info/version", "value": "2.0.0"},
+            {"op": "test", "path": "/info/version", "value": "1.0.0"}
+        ]));
+        assert!(failed_test.is_err());
+        // Nothing is changed when the patch fails.
+        assert_eq!(spec.info.version, "1.0.0");
+        let invalid_pointer =
+            spec.apply_patch(&json!([{"op": "replace", "path": "info/version", "value": "2"}]));
+        assert!(invalid_pointer.is_err());
+        let missing = spec.apply_patch(&json!([{"op": "remove", "path": "/info/missing"}]));
+        assert!(missing.is_err());
+        let invalid_document =
+            spec.apply_patch(&json!([{"op": "replace", "path": "/info", "value": 1}]));
+        assert!(invalid_document.is_err());
+        let invalid_op = spec.apply_patch(&json!([{"op": "rename", "path": "/info"}]));
+        assert!(invalid_op.is_err());
+    }
+}
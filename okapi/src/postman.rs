@@ -0,0 +1,275 @@
+use crate::diff::{operations, parameters};
+use crate::openapi3::{ExampleValue, MediaType, OpenApi, Operation, PathItem, RefOr};
+use serde_json::{json, Value};
+
+/// The schema of the collection format that is created.
+const POSTMAN_SCHEMA: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+impl OpenApi {
+    /// Convert the document to a [Postman](https://www.postman.com/) collection (v2.1), so it
+    /// can be imported into Postman.
+    ///
+    /// Every operation becomes a request in a folder with the name of its first tag, operations
+    /// without tags are added to the root of the collection. The URLs start with the
+    /// `{{baseUrl}}` variable, which is set to the url of the first server. Path parameters like
+    /// `{id}` are written as `:id`. The body of a request is the example of its
+    /// `application/json` request body, if there is one.
+    #[must_use]
+    pub fn to_postman_collection(&self) -> Value {
+        let mut root = Vec::new();
+        let mut folders: Vec<(String, Vec<Value>)> = Vec::new();
+        for (path, path_item) in &self.paths {
+            for (method, operation) in operations(path_item) {
+                let item = postman_item(self, path, path_item, method, operation);
+                match operation.tags.first() {
+                    Some(tag) => match folders.iter_mut().find(|(name, _)| name == tag) {
+                        Some((_, items)) => items.push(item),
+                        None => folders.push((tag.clone(), vec![item])),
+                    },
+                    None => root.push(item),
+                }
+            }
+        }
+        let mut items: Vec<Value> = folders
+            .into_iter()
+            .map(|(name, items)| json!({"name": name, "item": items}))
+            .collect();
+        items.extend(root);
+        let mut info = json!({
+            "name": self.info.title,
+            "schema": POSTMAN_SCHEMA,
+        });
+        if let Some(description) = &self.info.description {
+            info["description"] = json!(description);
+        }
+        let base_url = self
+            .servers
+            .first()
+            .map_or("", |server| server.url.as_str());
+        json!({
+            "info": info,
+            "item": items,
+            "variable": [{"key": "baseUrl", "value": base_url}],
+        })
+    }
+}
+
+/// Create the request item for a single operation.
+fn postman_item(
+    spec: &OpenApi,
+    path: &str,
+    path_item: &PathItem,
+    method: &str,
+    operation: &Operation,
+) -> Value {
+    let mut query = Vec::new();
+    let mut headers = Vec::new();
+    let mut variables = Vec::new();
+    for parameter in parameters(spec, path_item, operation) {
+        let entry = json!({"key": parameter.name, "value": ""});
+        match parameter.location.as_str() {
+            "query" => query.push(entry),
+            "header" => headers.push(entry),
+            "path" => variables.push(entry),
+            _ => {}
+        }
+    }
+    let segments: Vec<String> = path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| match segment.strip_prefix('{') {
+            Some(name) => format!(":{}", name.trim_end_matches('}')),
+            None => segment.to_owned(),
+        })
+        .collect();
+    let mut raw = format!("{{{{baseUrl}}}}/{}", segments.join("/"));
+    if !query.is_empty() {
+        let keys: Vec<String> = query
+            .iter()
+            .map(|entry| format!("{}=", entry["key"].as_str().unwrap_or_default()))
+            .collect();
+        raw = format!("{}?{}", raw, keys.join("&"));
+    }
+    let mut url = json!({
+        "raw": raw,
+        "host": ["{{baseUrl}}"],
+        "path": segments,
+    });
+    if !query.is_empty() {
+        url["query"] = json!(query);
+    }
+    if !variables.is_empty() {
+        url["variable"] = json!(variables);
+    }
+    let mut request = json!({
+        "method": method.to_uppercase(),
+        "header": headers,
+        "url": url,
+    });
+    if let Some(body) = json_body_example(operation) {
+        request["header"]
+            .as_array_mut()
+            .expect("The headers are an array.")
+            .push(json!({"key": "Content-Type", "value": "application/json"}));
+        request["body"] = json!({
+            "mode": "raw",
+            "raw": serde_json::to_string_pretty(&body).unwrap_or_default(),
+            "options": {"raw": {"language": "json"}},
+        });
+    }
+    let name = operation
+        .summary
+        .clone()
+        .or_else(|| operation.operation_id.clone())
+        .unwrap_or_else(|| format!("{} {}", method.to_uppercase(), path));
+    let mut item = json!({"name": name, "request": request});
+    if let Some(description) = &operation.description {
+        item["request"]["description"] = json!(description);
+    }
+    item
+}
+
+/// Get the example of the `application/json` request body of the operation, from the `example`
+/// or `examples` of the content or the `examples` of its schema.
+fn json_body_example(operation: &Operation) -> Option<Value> {
+    let media = match &operation.request_body {
+        Some(RefOr::Object(request_body)) => request_body.content.get("application/json")?,
+        _ => return None,
+    };
+    media_example(media)
+}
+
+fn media_example(media: &MediaType) -> Option<Value> {
+    if let Some(example) = &media.example {
+        return Some(example.clone());
+    }
+    let named = media
+        .examples
+        .iter()
+        .flatten()
+        .find_map(|(_, example)| match &example.value {
+            ExampleValue::Value(value) => Some(value.clone()),
+            ExampleValue::ExternalValue(_) => None,
+        });
+    named.or_else(|| {
+        let metadata = media.schema.as_ref()?.metadata.as_ref()?;
+        metadata.examples.first().cloned()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::openapi3::{Parameter, ParameterValue, RequestBody, Responses, Server};
+    use crate::Map;
+
+    fn path_parameter(name: &str) -> RefOr<Parameter> {
+        Parameter {
+            name: name.to_owned(),
+            location: "path".to_owned(),
+            description: None,
+            required: true,
+            deprecated: false,
+            allow_empty_value: false,
+            value: ParameterValue::Schema {
+                style: None,
+                explode: None,
+                allow_reserved: false,
+                schema: Default::default(),
+                example: None,
+                examples: None,
+            },
+            extensions: Default::default(),
+        }
+        .into()
+    }
+
+    fn example_spec() -> OpenApi {
+        let mut spec = OpenApi::new();
+        spec.info.title = "Pets".to_owned();
+        spec.info.version = "1.0.0".to_owned();
+        spec.servers.push(Server {
+            url: "https://api.example.com".to_owned(),
+            ..Server::default()
+        });
+        let mut content = Map::new();
+        content.insert(
+            "application/json".to_owned(),
+            MediaType {
+                example: Some(json!({"name": "Rex"})),
+                ..MediaType::default()
+            },
+        );
+        spec.paths.insert(
+            "/pets".to_owned(),
+            PathItem {
+                post: Some(Operation {
+                    tags: vec!["pets".to_owned()],
+                    summary: Some("Add a pet".to_owned()),
+                    request_body: Some(
+                        RequestBody {
+                            content,
+                            ..RequestBody::default()
+                        }
+                        .into(),
+                    ),
+                    responses: Responses::default(),
+                    ..Operation::default()
+                }),
+                ..PathItem::default()
+            },
+        );
+        spec.paths.insert(
+            "/pets/{id}".to_owned(),
+            PathItem {
+                get: Some(Operation {
+                    tags: vec!["pets".to_owned()],
+                    operation_id: Some("get_pet".to_owned()),
+                    parameters: vec![path_parameter("id")],
+                    ..Operation::default()
+                }),
+                ..PathItem::default()
+            },
+        );
+        spec
+    }
+
+    #[test]
+    fn test_to_postman_collection() {
+        let collection = example_spec().to_postman_collection();
+        assert_eq!(collection["info"]["name"], "Pets");
+        assert_eq!(collection["info"]["schema"], POSTMAN_SCHEMA);
+        assert_eq!(
+            collection["variable"],
+            json!([{"key": "baseUrl", "value": "https://api.example.com"}])
+        );
+        let folders = collection["item"].as_array().unwrap();
+        assert_eq!(folders.len(), 1);
+        assert_eq!(folders[0]["name"], "pets");
+        let items = folders[0]["item"].as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        let mut urls: Vec<_> = items
+            .iter()
+            .map(|item| item["request"]["url"]["raw"].as_str().unwrap())
+            .collect();
+        urls.sort_unstable();
+        assert_eq!(urls, vec!["{{baseUrl}}/pets", "{{baseUrl}}/pets/:id"]);
+
+        let post = items
+            .iter()
+            .find(|item| item["name"] == "Add a pet")
+            .unwrap();
+        assert_eq!(post["request"]["method"], "POST");
+        assert_eq!(post["request"]["body"]["mode"], "raw");
+        let body: Value =
+            serde_json::from_str(post["request"]["body"]["raw"].as_str().unwrap()).unwrap();
+        assert_eq!(body, json!({"name": "Rex"}));
+        let get = items.iter().find(|item| item["name"] == "get_pet").unwrap();
+        assert_eq!(get["request"]["method"], "GET");
+        assert_eq!(get["request"]["url"]["path"], json!(["pets", ":id"]));
+        assert_eq!(
+            get["request"]["url"]["variable"],
+            json!([{"key": "id", "value": ""}])
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use crate::openapi3::OpenApi;
+use serde_json::Value;
+
+impl OpenApi {
+    /// Check that every local `$ref` in the document points to an existing item.
+    /// For example `#/components/schemas/User` needs `User` to be in `components.schemas`.
+    ///
+    /// Returns the list of references that could not be resolved.
+    /// References to other documents are not checked.
+    pub fn validate_refs(&self) -> Result<(), Vec<String>> {
+        let document = serde_json::to_value(self).expect("Could not serialize OpenApi as JSON.");
+        let mut references = Vec::new();
+        collect_references(&document, &mut references);
+        let mut missing: Vec<String> = Vec::new();
+        for reference in references {
+            if let Some(pointer) = reference.strip_prefix('#') {
+                if document.pointer(pointer).is_none() && !missing.contains(&reference) {
+                    missing.push(reference);
+                }
+            }
+        }
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(missing)
+        }
+    }
+}
+
+/// Collect all the `$ref` values in the document.
+fn collect_references(value: &Value, references: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    Value::String(reference) if key == "$ref" => {
+                        references.push(reference.clone());
+                    }
+                    _ => collect_references(value, references),
+                }
+            }
+        }
+        Value::Array(list) => {
+            for value in list {
+                collect_references(value, references);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::openapi3::*;
+
+    fn spec_with_ref(reference: &str) -> OpenApi {
+        let mut spec = OpenApi::new();
+        let mut components = Components::default();
+        components
+            .schemas
+            .insert("User".to_owned(), SchemaObject::default());
+        spec.components = Some(components);
+        let mut responses = Responses::default();
+        let mut content = crate::Map::new();
+        content.insert(
+            "application/json".to_owned(),
+            MediaType {
+                schema: Some(SchemaObject::new_ref(reference.to_owned())),
+                ..MediaType::default()
+            },
+        );
+        responses.responses.insert(
+            "200".to_owned(),
+            Response {
+                content,
+                ..Response::default()
+            }
+            .into(),
+        );
+        spec.paths.insert(
+            "/user".to_owned(),
+            PathItem {
+                get: Some(Operation {
+                    responses,
+                    ..Operation::default()
+                }),
+                ..PathItem::default()
+            },
+        );
+        spec
+    }
+
+    #[test]
+    fn test_validate_refs() {
+        assert_eq!(
+            spec_with_ref("#/components/schemas/User").validate_refs(),
+            Ok(())
+        );
+        assert_eq!(
+            spec_with_ref("#/components/schemas/Missing").validate_refs(),
+            Err(vec!["#/components/schemas/Missing".to_owned()])
+        );
+    }
+}
@@ -10,9 +10,6 @@ pub fn create_openapi_spec(routes: TokenStream) -> Result<TokenStream2> {
     let add_operations = create_add_operations(paths);
     Ok(quote! {
         |settings: &::rocket_okapi::settings::OpenApiSettings| -> ::rocket_okapi::okapi::openapi3::OpenApi {
-            let mut gen = ::rocket_okapi::gen::OpenApiGenerator::new(settings);
-            #add_operations
-            let mut spec = gen.into_openapi();
             let mut info = ::rocket_okapi::okapi::openapi3::Info {
                 title: env!("CARGO_PKG_NAME").to_owned(),
                 version: env!("CARGO_PKG_VERSION").to_owned(),
@@ -35,7 +32,22 @@ pub fn create_openapi_spec(routes: TokenStream) -> Result<TokenStream2> {
                     ..Default::default()
                 });
             }
-            spec.info = info;
+            // Only use the info from `Cargo.toml` when no info was set in the settings.
+            let settings = if settings.info == ::rocket_okapi::okapi::openapi3::Info::default() {
+                settings.clone().with_info(info)
+            } else {
+                settings.clone()
+            };
+            let mut gen = ::rocket_okapi::gen::OpenApiGenerator::new(&settings);
+            #add_operations
+            let spec = if settings.strict {
+                gen.into_openapi_strict().unwrap_or_else(|errors| {
+                    let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+                    panic!("The OpenAPI document is incomplete:\n{}", errors.join("\n"))
+                })
+            } else {
+                gen.into_openapi()
+            };
 
             spec
         }
@@ -57,7 +69,7 @@ fn create_add_operations(paths: Punctuated<Path, Comma>) -> TokenStream2 {
 }
 
 fn fn_name_for_add_operation(mut fn_path: Path) -> Path {
-    let mut last_seg = fn_path.segments.last_mut().expect("syn::Path has segments");
+    let last_seg = fn_path.segments.last_mut().expect("syn::Path has segments");
     last_seg.ident = get_add_operation_fn_name(&last_seg.ident);
     fn_path
 }
@@ -68,5 +80,5 @@ fn operation_id(fn_path: &Path) -> String {
         .iter()
         .map(|s| s.ident.to_string())
         .collect();
-    idents.join("_")
+    idents.join("::")
 }
@@ -1,34 +1,292 @@
 use okapi::merge::MergeError;
+use okapi::patch::PatchError;
+use rocket::http::Method;
 use std::error::Error;
 use std::fmt;
+use std::sync::Arc;
 
 /// Type alias for `Result<T, OpenApiError>`.
 pub type Result<T> = std::result::Result<T, OpenApiError>;
 
 /// The error type returned by `rocket_okapi` when something fails.
 #[derive(Debug, Clone)]
-pub struct OpenApiError {
-    msg: String,
+#[non_exhaustive]
+pub enum OpenApiError {
+    /// A general error described by a message.
+    Message(String),
+    /// An operation was already added for the same path and HTTP method.
+    DuplicateOperation {
+        /// The path of the conflicting operation.
+        path: String,
+        /// The HTTP method of the conflicting operation.
+        method: Method,
+    },
+    /// No operation was added for the path and HTTP method.
+    MissingOperation {
+        /// The path of the missing operation.
+        path: String,
+        /// The HTTP method of the missing operation.
+        method: Method,
+    },
+    /// The operation has no parameter with the name.
+    MissingParameter {
+        /// The path of the operation.
+        path: String,
+        /// The HTTP method of the operation.
+        method: Method,
+        /// The name of the missing parameter.
+        name: String,
+    },
+    /// The HTTP method of the operation can not be added to the document.
+    UnsupportedMethod {
+        /// The path of the operation.
+        path: String,
+        /// The HTTP method of the operation.
+        method: Method,
+    },
+    /// Multiple operations have the same `operationId`.
+    DuplicateOperationId {
+        /// The `operationId` used by more than one operation.
+        operation_id: String,
+    },
+    /// The path contains a `{name}` placeholder, but the operation has no path parameter with
+    /// this name.
+    MissingPathParameter {
+        /// The path of the operation.
+        path: String,
+        /// The HTTP method of the operation.
+        method: Method,
+        /// The name of the placeholder.
+        name: String,
+    },
+    /// The operation has a path parameter that is not a placeholder in the path.
+    UnknownPathParameter {
+        /// The path of the operation.
+        path: String,
+        /// The HTTP method of the operation.
+        method: Method,
+        /// The name of the parameter.
+        name: String,
+    },
+    /// A link of a response refers to an `operationId` that is not in the document.
+    UnknownLinkOperation {
+        /// The name of the link.
+        name: String,
+        /// The `operationId` that does not exist.
+        operation_id: String,
+    },
+    /// A required field of the `info` of the document is empty.
+    EmptyInfoField {
+        /// The name of the empty field, like `title`.
+        field: &'static str,
+    },
+    /// A `$ref` in the document does not point to an existing item.
+    UnresolvedRef {
+        /// The reference that could not be resolved.
+        reference: String,
+    },
+    /// The operation does not have any responses.
+    MissingResponses {
+        /// The path of the operation.
+        path: String,
+        /// The HTTP method of the operation.
+        method: Method,
+    },
+    /// An example does not match the schema of its content.
+    InvalidExample {
+        /// The path of the operation.
+        path: String,
+        /// The HTTP method of the operation.
+        method: Method,
+        /// The content type of the example.
+        content_type: String,
+    },
+    /// An operation has no `summary`, or the `summary` is empty.
+    MissingSummary {
+        /// The path of the operation.
+        path: String,
+        /// The HTTP method of the operation.
+        method: Method,
+    },
+    /// A value could not be converted to or from JSON. The error is in an `Arc`, so `OpenApiError`
+    /// can still be cloned.
+    Serialization(Arc<serde_json::Error>),
+    /// The key of an extension does not start with `x-`.
+    InvalidExtensionKey {
+        /// The invalid key.
+        key: String,
+    },
 }
 
 impl OpenApiError {
     /// Create a new `OpenApiError` containing a message.
     #[must_use]
     pub fn new(msg: String) -> Self {
-        OpenApiError { msg }
+        OpenApiError::Message(msg)
     }
 }
 
 impl fmt::Display for OpenApiError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.msg)
+        match self {
+            Self::Message(msg) => write!(f, "{}", msg),
+            Self::DuplicateOperation { path, method } => {
+                write!(
+                    f,
+                    "An operation for `{} {}` was already added.",
+                    method, path
+                )
+            }
+            Self::MissingOperation { path, method } => {
+                write!(f, "No operation for `{} {}` was added.", method, path)
+            }
+            Self::MissingParameter { path, method, name } => write!(
+                f,
+                "The operation for `{} {}` has no parameter named `{}`.",
+                method, path, name
+            ),
+            Self::UnsupportedMethod { path, method } => {
+                write!(
+                    f,
+                    "The operation for `{} {}` can not be added, OpenAPI does not support `{}`.",
+                    method, path, method
+                )
+            }
+            Self::DuplicateOperationId { operation_id } => write!(
+                f,
+                "The operationId `{}` is used by multiple operations.",
+                operation_id
+            ),
+            Self::MissingPathParameter { path, method, name } => write!(
+                f,
+                "The path of `{} {}` contains `{{{}}}`, but the operation has no path \
+                parameter with this name.",
+                method, path, name
+            ),
+            Self::UnknownPathParameter { path, method, name } => write!(
+                f,
+                "The operation for `{} {}` has the path parameter `{}`, but the path does not \
+                contain `{{{}}}`.",
+                method, path, name, name
+            ),
+            Self::UnknownLinkOperation { name, operation_id } => write!(
+                f,
+                "The link `{}` refers to the operationId `{}`, which does not exist.",
+                name, operation_id
+            ),
+            Self::EmptyInfoField { field } => {
+                write!(f, "The `info.{}` of the document is empty.", field)
+            }
+            Self::UnresolvedRef { reference } => {
+                write!(f, "The reference `{}` could not be resolved.", reference)
+            }
+            Self::MissingResponses { path, method } => {
+                write!(
+                    f,
+                    "The operation for `{} {}` has no responses.",
+                    method, path
+                )
+            }
+            Self::InvalidExample {
+                path,
+                method,
+                content_type,
+            } => write!(
+                f,
+                "The example for `{}` of `{} {}` does not match its schema.",
+                content_type, method, path
+            ),
+            Self::MissingSummary { path, method } => {
+                write!(f, "The operation for `{} {}` has no summary.", method, path)
+            }
+            Self::Serialization(err) => write!(f, "Could not convert to or from JSON: {}", err),
+            Self::InvalidExtensionKey { key } => {
+                write!(f, "The extension `{}` does not start with `x-`.", key)
+            }
+        }
     }
 }
 
-impl Error for OpenApiError {}
+impl Error for OpenApiError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Serialization(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for OpenApiError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Serialization(Arc::new(error))
+    }
+}
 
 impl From<MergeError> for OpenApiError {
     fn from(error: MergeError) -> Self {
         Self::new(error.msg)
     }
 }
+
+impl From<PatchError> for OpenApiError {
+    fn from(error: PatchError) -> Self {
+        Self::new(error.msg)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let errors = vec![
+            (
+                OpenApiError::DuplicateOperation {
+                    path: "/user".to_owned(),
+                    method: Method::Get,
+                },
+                "An operation for `GET /user` was already added.",
+            ),
+            (
+                OpenApiError::MissingOperation {
+                    path: "/user".to_owned(),
+                    method: Method::Post,
+                },
+                "No operation for `POST /user` was added.",
+            ),
+            (
+                OpenApiError::UnresolvedRef {
+                    reference: "#/components/schemas/User".to_owned(),
+                },
+                "The reference `#/components/schemas/User` could not be resolved.",
+            ),
+            (
+                OpenApiError::InvalidExtensionKey {
+                    key: "custom".to_owned(),
+                },
+                "The extension `custom` does not start with `x-`.",
+            ),
+        ];
+        for (error, message) in errors {
+            assert_eq!(error.to_string(), message);
+            assert!(error.source().is_none());
+        }
+    }
+
+    #[test]
+    fn test_from_serde_json_error() {
+        fn parse() -> Result<serde_json::Value> {
+            Ok(serde_json::from_str("{")?)
+        }
+        let error = parse().unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Could not convert to or from JSON: "));
+        assert!(error.source().is_some());
+        assert!(matches!(
+            error.clone(),
+            OpenApiError::Serialization(inner) if inner.is_eof()
+        ));
+    }
+}
@@ -0,0 +1,229 @@
+use crate::settings::{DocRenderer, OpenApiSettings};
+use okapi::openapi3::OpenApi;
+use rocket::fairing::{self, Fairing, Info, Kind};
+use rocket::{Build, Rocket, Route};
+
+/// A fairing that mounts routes together with their documentation, and serves the merged `OpenApi`
+/// document (and the documentation UI) when Rocket ignites. This does the same as
+/// [`mount_endpoints_and_merged_docs`](crate::mount_endpoints_and_merged_docs) without a macro.
+///
+/// The document is served at `OpenApiSettings::json_path`. When `OpenApiSettings::doc_renderer`
+/// is set, the UI is served at `OpenApiSettings::docs_path`. Swagger UI can be added using
+/// [`swagger_ui`](Self::swagger_ui). Other routes can read the document as `&State<OpenApi>`
+/// after calling [`manage_spec`](Self::manage_spec).
+///
+/// ```rust,ignore
+/// use rocket_okapi::fairing::OpenApiFairing;
+/// use rocket_okapi::settings::OpenApiSettings;
+///
+/// let settings = OpenApiSettings::default();
+/// let fairing = OpenApiFairing::new(&settings)
+///     .mount("/users", openapi_get_routes_spec![settings: get_user, create_user]);
+/// rocket::build().attach(fairing)
+/// ```
+#[derive(Clone)]
+pub struct OpenApiFairing {
+    // Only the settings used to serve the document are kept, `OpenApiSettings` is not `Sync`.
+    json_path: String,
+    #[cfg(feature = "yaml")]
+    yaml_path: Option<String>,
+    doc_renderer: Option<DocRenderer>,
+    docs_path: String,
+    manage_spec: bool,
+    mounts: Vec<(String, Vec<Route>, OpenApi)>,
+    #[cfg(feature = "swagger")]
+    swagger_ui: Option<(String, crate::swagger_ui::SwaggerUIConfig)>,
+}
+
+impl OpenApiFairing {
+    /// Create a new `OpenApiFairing` that serves the document using the `json_path`, `yaml_path`,
+    /// `doc_renderer` and `docs_path` of the `settings`.
+    #[must_use]
+    pub fn new(settings: &OpenApiSettings) -> Self {
+        OpenApiFairing {
+            json_path: settings.json_path.clone(),
+            #[cfg(feature = "yaml")]
+            yaml_path: settings.yaml_path.clone(),
+            doc_renderer: settings.doc_renderer.clone(),
+            docs_path: settings.docs_path.clone(),
+            manage_spec: false,
+            mounts: Vec::new(),
+            #[cfg(feature = "swagger")]
+            swagger_ui: None,
+        }
+    }
+
+    /// Mount the routes at the `path` and add their documentation to the `OpenApi` document,
+    /// the paths in the documentation get the same prefix. The `routes_and_spec` is what
+    /// [`openapi_get_routes_spec`](crate::openapi_get_routes_spec) returns.
+    #[must_use]
+    pub fn mount(
+        mut self,
+        path: impl Into<String>,
+        routes_and_spec: (Vec<Route>, OpenApi),
+    ) -> Self {
+        let (routes, spec) = routes_and_spec;
+        self.mounts.push((path.into(), routes, spec));
+        self
+    }
+
+    /// Add the merged `OpenApi` document to the managed state of Rocket, so routes can use it
+    /// with a `&State<OpenApi>` guard, for example to report the number of paths.
+    /// Rocket fails to launch if an `OpenApi` is already managed.
+    #[must_use]
+    pub fn manage_spec(mut self) -> Self {
+        self.manage_spec = true;
+        self
+    }
+
+    /// Serve Swagger UI at the `path`. When the `url` and `urls` of the `config` are empty, the
+    /// `url` is set to the `OpenApiSettings::json_path`.
+    #[cfg(feature = "swagger")]
+    #[must_use]
+    pub fn swagger_ui(
+        mut self,
+        path: impl Into<String>,
+        config: crate::swagger_ui::SwaggerUIConfig,
+    ) -> Self {
+        self.swagger_ui = Some((path.into(), config));
+        self
+    }
+
+    /// Merge the documentation of all the mounted routes.
+    fn merged_spec(&self) -> Result<OpenApi, okapi::merge::MergeError> {
+        let specs: Vec<(&str, OpenApi)> = self
+            .mounts
+            .iter()
+            .map(|(path, _, spec)| (path.trim_end_matches('/'), spec.clone()))
+            .collect();
+        okapi::merge::marge_spec_list(&specs)
+    }
+
+    fn settings(&self) -> OpenApiSettings {
+        OpenApiSettings {
+            json_path: self.json_path.clone(),
+            #[cfg(feature = "yaml")]
+            yaml_path: self.yaml_path.clone(),
+            doc_renderer: self.doc_renderer.clone(),
+            docs_path: self.docs_path.clone(),
+            ..OpenApiSettings::default()
+        }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for OpenApiFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "OpenAPI",
+            kind: Kind::Ignite,
+        }
+    }
+
+    async fn on_ignite(&self, mut rocket: Rocket<Build>) -> fairing::Result {
+        let spec = match self.merged_spec() {
+            Ok(spec) => spec,
+            Err(err) => {
+                log::error!("Could not merge OpenAPI spec: {}", err);
+                return Err(rocket);
+            }
+        };
+        for (path, routes, _) in &self.mounts {
+            rocket = rocket.mount(path.as_str(), routes.clone());
+        }
+        #[cfg(feature = "swagger")]
+        if let Some((path, config)) = &self.swagger_ui {
+            let mut config = config.clone();
+            if config.url.is_empty() && config.urls.is_empty() {
+                config.url = self.json_path.clone();
+            }
+            rocket = rocket.mount(path.as_str(), crate::swagger_ui::make_swagger_ui(&config));
+        }
+        if self.manage_spec {
+            rocket = rocket.manage(spec.clone());
+        }
+        Ok(rocket.mount("/", crate::get_openapi_routes(spec, &self.settings())))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use okapi::openapi3::{Operation, PathItem};
+    use rocket::http::{Method, Status};
+    use rocket::local::blocking::Client;
+    use rocket::route::{BoxFuture, Outcome};
+    use rocket::{Data, Request};
+
+    fn hello<'r>(req: &'r Request<'_>, _: Data<'r>) -> BoxFuture<'r> {
+        Outcome::from(req, "Hello").pin()
+    }
+
+    fn path_count<'r>(req: &'r Request<'_>, _: Data<'r>) -> BoxFuture<'r> {
+        let spec = req
+            .rocket()
+            .state::<OpenApi>()
+            .expect("The spec is managed.");
+        Outcome::from(req, spec.paths.len().to_string()).pin()
+    }
+
+    fn routes_and_spec() -> (Vec<Route>, OpenApi) {
+        let mut spec = OpenApi::new();
+        spec.paths.insert(
+            "/hello".to_owned(),
+            PathItem {
+                get: Some(Operation {
+                    operation_id: Some("hello".to_owned()),
+                    ..Operation::default()
+                }),
+                ..PathItem::default()
+            },
+        );
+        (vec![Route::new(Method::Get, "/hello", hello)], spec)
+    }
+
+    #[test]
+    fn test_fairing() {
+        #[allow(unused_mut)]
+        let mut settings = OpenApiSettings::default();
+        #[cfg(feature = "rapidoc")]
+        {
+            settings.doc_renderer = Some(DocRenderer::RapiDoc(Default::default()));
+        }
+        let fairing = OpenApiFairing::new(&settings).mount("/v1", routes_and_spec());
+        let client = Client::tracked(rocket::build().attach(fairing)).unwrap();
+
+        let response = client.get("/v1/hello").dispatch();
+        assert_eq!(response.into_string().as_deref(), Some("Hello"));
+        let response = client.get("/openapi.json").dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let spec: OpenApi = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+        assert!(spec.paths.contains_key("/v1/hello"));
+
+        #[cfg(feature = "rapidoc")]
+        {
+            let response = client.get("/docs").dispatch();
+            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(
+                response.content_type(),
+                Some(rocket::http::ContentType::HTML)
+            );
+            assert!(response.into_string().unwrap().contains("openapi.json"));
+        }
+    }
+
+    #[test]
+    fn test_manage_spec() {
+        let health_spec = (
+            vec![Route::new(Method::Get, "/count", path_count)],
+            OpenApi::new(),
+        );
+        let fairing = OpenApiFairing::new(&OpenApiSettings::default())
+            .mount("/v1", routes_and_spec())
+            .mount("/health", health_spec)
+            .manage_spec();
+        let client = Client::tracked(rocket::build().attach(fairing)).unwrap();
+        let response = client.get("/health/count").dispatch();
+        assert_eq!(response.into_string().as_deref(), Some("1"));
+    }
+}
@@ -1,12 +1,183 @@
-use crate::settings::OpenApiSettings;
-use crate::OperationInfo;
-use okapi::openapi3::{Components, OpenApi, Operation, PathItem, RefOr, SecurityScheme};
+use crate::settings::{
+    CollisionPolicy, ConnectHandling, OpenApiSettings, OpenApiVersion, OperationIdCollision,
+    SchemaNaming, TrailingSlash,
+};
+use crate::util::{
+    add_content_response, add_media_type, add_schema_response, ensure_status_code_exists,
+    value_matches_schema,
+};
+use crate::visit::{
+    visit_openapi, visit_operation, visit_parameter, visit_path_item, visit_response,
+    ApplySchemaVisitor, InlineReferences, ReplaceNullTypeWithNullable, ReplaceNullableWithNullType,
+    ReplaceReferences, UnwrapRefWithSiblings, WrapRefWithSiblings,
+};
+use crate::{OpenApiError, OpenApiWarning, OperationInfo, Result};
+use okapi::openapi3::{
+    Callback, Components, Discriminator, Encoding, Example, ExternalDocs, Header, Link, MediaType,
+    Object, OpenApi, Operation, Parameter, ParameterStyle, ParameterValue, PathItem, Ref, RefOr,
+    RequestBody, Response, Responses, SecurityRequirement, SecurityScheme, Server, Tag,
+};
 use okapi::{Map, MapEntry};
 use rocket::http::Method;
-use schemars::gen::SchemaGenerator;
-use schemars::schema::SchemaObject;
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
+use schemars::visit::{SetSingleExample, Visitor};
 use schemars::JsonSchema;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// The allowed values of properties that are not listed in `properties`, used by
+/// [`OpenApiGenerator::set_additional_properties`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AdditionalProperties {
+    /// Allow (`true`) or forbid (`false`) all other properties.
+    Bool(bool),
+    /// Other properties need to match the schema.
+    Schema(Box<RefOr<SchemaObject>>),
+}
+
+/// The casing of property names, used by [`OpenApiGenerator::check_serde_casing`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyCasing {
+    /// Like `userName`.
+    CamelCase,
+    /// Like `UserName`.
+    PascalCase,
+    /// Like `user_name`.
+    SnakeCase,
+    /// Like `USER_NAME`.
+    ScreamingSnakeCase,
+    /// Like `user-name`.
+    KebabCase,
+}
+
+impl PropertyCasing {
+    /// Returns `true` if the `name` uses this casing.
+    #[must_use]
+    pub fn matches(self, name: &str) -> bool {
+        let has_upper = name.chars().any(|c| c.is_uppercase());
+        let has_lower = name.chars().any(|c| c.is_lowercase());
+        let starts_upper = matches!(name.chars().next(), Some(c) if c.is_uppercase());
+        let has_underscore = name.contains('_');
+        let has_dash = name.contains('-');
+        match self {
+            PropertyCasing::CamelCase => !starts_upper && !has_underscore && !has_dash,
+            PropertyCasing::PascalCase => starts_upper && !has_underscore && !has_dash,
+            PropertyCasing::SnakeCase => !has_upper && !has_dash,
+            PropertyCasing::ScreamingSnakeCase => !has_lower && !has_dash,
+            PropertyCasing::KebabCase => !has_upper && !has_underscore,
+        }
+    }
+}
+
+/// Whether a property is only sent by the server, only sent by the client or both, used by
+/// [`OpenApiGenerator::set_property_access`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyAccess {
+    /// The property is only in responses (`readOnly`), like an `id` set by the server.
+    ReadOnly,
+    /// The property is only in requests (`writeOnly`), like a `password`.
+    WriteOnly,
+    /// The property is in both requests and responses, removes both flags.
+    ReadWrite,
+}
+
+/// Documentation-only validation keywords for a property, used by
+/// [`OpenApiGenerator::set_property_constraints`]. Only the fields that are `Some` are set.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Constraints {
+    /// The `minimum` of a number or integer.
+    pub minimum: Option<f64>,
+    /// The `maximum` of a number or integer.
+    pub maximum: Option<f64>,
+    /// The `minLength` of a string.
+    pub min_length: Option<u32>,
+    /// The `maxLength` of a string.
+    pub max_length: Option<u32>,
+    /// The `pattern` (a regular expression) of a string.
+    pub pattern: Option<String>,
+}
+
+/// A part of a `multipart/form-data` request body, used by
+/// [`OpenApiGenerator::add_multipart_body`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultipartPart {
+    /// The name of the part, this is the name of the property in the schema.
+    pub name: String,
+    /// The schema of the part.
+    pub schema: SchemaObject,
+    /// If the part is required. Default: `true`.
+    pub required: bool,
+    /// The content type of the part, like `application/json` or `image/png`.
+    /// When `None` the default content type for the schema is used. Default: `None`.
+    pub content_type: Option<String>,
+    /// Headers of the part, other than `Content-Type`. Default: empty.
+    pub headers: Map<String, RefOr<Header>>,
+}
+
+impl MultipartPart {
+    /// Create a required part with the `schema`, for example created with
+    /// [`OpenApiGenerator::json_schema`].
+    #[must_use]
+    pub fn new(name: impl Into<String>, schema: SchemaObject) -> Self {
+        MultipartPart {
+            name: name.into(),
+            schema,
+            required: true,
+            content_type: None,
+            headers: Map::new(),
+        }
+    }
+
+    /// Create a required part for raw bytes like a file, the schema is a `string` with the
+    /// `binary` format.
+    #[must_use]
+    pub fn binary(name: impl Into<String>) -> Self {
+        MultipartPart::new(name, binary_schema())
+    }
+
+    /// Set if the part is required.
+    #[must_use]
+    pub fn with_required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Set the content type of the part.
+    #[must_use]
+    pub fn with_content_type(mut self, content_type: impl Into<String>) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// Add a header to the part.
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, header: Header) -> Self {
+        self.headers.insert(name.into(), header.into());
+        self
+    }
+}
+
+/// A type whose schema can be generated on another thread by
+/// [`OpenApiGenerator::json_schemas_parallel`].
+#[derive(Debug, Clone)]
+pub struct ParallelSchema {
+    schema_id: Cow<'static, str>,
+    generate: fn(&mut SchemaGenerator) -> Schema,
+}
+
+impl ParallelSchema {
+    /// Create a `ParallelSchema` for the type `T`.
+    #[must_use]
+    pub fn of<T: ?Sized + JsonSchema>() -> Self {
+        ParallelSchema {
+            schema_id: T::schema_id(),
+            generate: SchemaGenerator::subschema_for::<T>,
+        }
+    }
+}
 
 /// A struct that visits all `rocket::Route`s, and aggregates information about them.
 #[derive(Debug, Clone)]
@@ -14,7 +185,21 @@ pub struct OpenApiGenerator {
     settings: OpenApiSettings,
     schema_generator: SchemaGenerator,
     security_schemes: Map<String, SecurityScheme>,
+    responses: Map<String, Response>,
+    catcher_responses: Map<String, Response>,
+    parameters: Map<String, Parameter>,
+    default_security: Vec<SecurityRequirement>,
+    global_security: Vec<SecurityRequirement>,
     operations: Map<String, HashMap<Method, Operation>>,
+    tags: Vec<Tag>,
+    servers: Vec<Server>,
+    path_servers: Map<String, Vec<Server>>,
+    path_parameters: Map<String, Vec<RefOr<Parameter>>>,
+    webhooks: Map<String, PathItem>,
+    external_docs: Option<ExternalDocs>,
+    extensions: Map<String, serde_json::Value>,
+    warnings: Vec<OpenApiWarning>,
+    schema_keys: Map<String, String>,
 }
 
 impl OpenApiGenerator {
@@ -25,7 +210,21 @@ impl OpenApiGenerator {
             schema_generator: settings.schema_settings.clone().into_generator(),
             settings: settings.clone(),
             security_schemes: Map::default(),
+            responses: Map::default(),
+            catcher_responses: Map::default(),
+            parameters: Map::default(),
+            default_security: Vec::new(),
+            global_security: Vec::new(),
             operations: Map::default(),
+            tags: Vec::new(),
+            servers: Vec::new(),
+            path_servers: Map::default(),
+            path_parameters: Map::default(),
+            webhooks: Map::default(),
+            external_docs: None,
+            extensions: Map::default(),
+            warnings: Vec::new(),
+            schema_keys: Map::default(),
         }
     }
 
@@ -34,100 +233,5604 @@ impl OpenApiGenerator {
         self.security_schemes.insert(name, scheme);
     }
 
-    /// Add a new `HTTP Method` to the collection of endpoints in the `OpenApiGenerator`.
-    pub fn add_operation(&mut self, mut op: OperationInfo) {
-        if let Some(op_id) = op.operation.operation_id {
-            // TODO do this outside add_operation
-            op.operation.operation_id = Some(op_id.trim_start_matches(':').replace("::", "_"));
+    /// Adds/Replace a response to the `components.responses` of the generated output.
+    /// Operations can use it with [`use_component_response`](Self::use_component_response).
+    pub fn add_component_response(&mut self, name: &str, response: Response) {
+        self.responses.insert(name.to_owned(), response);
+    }
+
+    /// Set the response with the `status` code of an added operation to a reference to the
+    /// response with the `name` in `components.responses`, like
+    /// `#/components/responses/NotFound`. Use `0` as status for the `default` response.
+    ///
+    /// Returns an [`OpenApiError::MissingOperation`] error if no operation was added for the
+    /// path and method. A reference to a response that is never added results in an
+    /// [`OpenApiWarning::UnresolvedRef`] warning.
+    pub fn use_component_response(
+        &mut self,
+        path: &str,
+        method: Method,
+        status: u16,
+        name: &str,
+    ) -> Result<()> {
+        let responses = &mut self.operation_mut(path, method)?.responses;
+        let response = RefOr::Ref(Ref {
+            reference: format!("#/components/responses/{}", name),
+        });
+        if status == 0 {
+            responses.default = Some(response);
+        } else {
+            responses.responses.insert(status.to_string(), response);
+        }
+        Ok(())
+    }
+
+    /// Adds/Replace the response of a Rocket catcher (error handler) for the `status` code, like a
+    /// `404` catcher. Use `0` as status for the `default` response, like a default catcher.
+    ///
+    /// The response is added to every operation that does not have a response with the same
+    /// status code. When [`OpenApiSettings::inject_catcher_responses`] is `false` the operations
+    /// are not changed, and the response is added to `components.responses` with the status code
+    /// (or `default`) as name instead, so it can be used with
+    /// [`use_component_response`](Self::use_component_response).
+    pub fn add_catcher_response(&mut self, status: u16, response: Response) {
+        let status = if status == 0 {
+            "default".to_owned()
+        } else {
+            status.to_string()
+        };
+        self.catcher_responses.insert(status, response);
+    }
+
+    /// Adds/Replace a parameter to the `components.parameters` of the generated output.
+    /// Operations can use it with [`use_component_parameter`](Self::use_component_parameter).
+    pub fn add_component_parameter(&mut self, name: &str, param: Parameter) {
+        self.parameters.insert(name.to_owned(), param);
+    }
+
+    /// Add a reference to the parameter with the `name` in `components.parameters` to the
+    /// parameters of an added operation, like `#/components/parameters/PageParam`.
+    ///
+    /// Returns an [`OpenApiError::MissingOperation`] error if no operation was added for the
+    /// path and method. A reference to a parameter that is never added results in an
+    /// [`OpenApiWarning::UnresolvedRef`] warning.
+    pub fn use_component_parameter(
+        &mut self,
+        path: &str,
+        method: Method,
+        name: &str,
+    ) -> Result<()> {
+        self.operation_mut(path, method)?
+            .parameters
+            .push(RefOr::Ref(Ref {
+                reference: format!("#/components/parameters/{}", name),
+            }));
+        Ok(())
+    }
+
+    /// Adds/Replace a callback with the `name` in the `callbacks` of an added operation.
+    /// The keys of the `Callback` are runtime expressions, like `{$request.body#/callbackUrl}`.
+    ///
+    /// Returns an [`OpenApiError::MissingOperation`] error if no operation was added for the
+    /// path and method.
+    pub fn add_callback(
+        &mut self,
+        path: &str,
+        method: Method,
+        name: &str,
+        callback: Callback,
+    ) -> Result<()> {
+        self.operation_mut(path, method)?
+            .callbacks
+            .insert(name.to_owned(), callback.into());
+        Ok(())
+    }
+
+    /// Add a security requirement to the `security` of an added operation.
+    /// The schemes used in the requirement should be added using
+    /// [`add_security_scheme`](Self::add_security_scheme).
+    ///
+    /// Returns an [`OpenApiError::MissingOperation`] error if no operation was added for the
+    /// path and method.
+    pub fn require_security(
+        &mut self,
+        path: &str,
+        method: Method,
+        requirement: SecurityRequirement,
+    ) -> Result<()> {
+        self.operation_mut(path, method)?
+            .security
+            .get_or_insert_with(Vec::new)
+            .push(requirement);
+        Ok(())
+    }
+
+    /// Add a security requirement that is used for all operations that do not have a
+    /// `security` of their own.
+    pub fn add_default_security(&mut self, requirement: SecurityRequirement) {
+        self.default_security.push(requirement);
+    }
+
+    /// Set the document-wide `security` of the generated output, which applies to all operations
+    /// that do not have a `security` of their own. An operation can opt out using an empty
+    /// `security` list. Unlike [`add_default_security`](Self::add_default_security), the
+    /// requirements are not copied into the operations.
+    ///
+    /// Adds an [`OpenApiWarning::UnknownSecurityScheme`] warning in
+    /// [`into_openapi_with_warnings`](Self::into_openapi_with_warnings) for every scheme that was
+    /// not added using [`add_security_scheme`](Self::add_security_scheme).
+    pub fn set_global_security(&mut self, requirements: Vec<SecurityRequirement>) {
+        self.global_security = requirements;
+    }
+
+    /// Adds/Replace a tag with the same name to the global `tags` of the generated output.
+    /// Tags are listed in the order they were first added.
+    pub fn add_tag(&mut self, tag: Tag) {
+        match self
+            .tags
+            .iter_mut()
+            .find(|existing| existing.name == tag.name)
+        {
+            Some(existing) => *existing = tag,
+            None => self.tags.push(tag),
+        }
+    }
+
+    /// Add a server to the `servers` of the generated output.
+    /// The `url` can contain variables like `{environment}.api.example.com`,
+    /// these should be described in `Server::variables`.
+    ///
+    /// The server is always added, problems with its variables are added to the
+    /// [`warnings`](Self::warnings). Use [`try_add_server`](Self::try_add_server) to handle
+    /// them yourself.
+    pub fn add_server(&mut self, server: Server) {
+        if let Err(err) = validate_server(&server) {
+            self.add_warning(OpenApiWarning::InvalidServer(err));
         }
-        match self.operations.entry(op.path) {
-            MapEntry::Occupied(mut e) => {
-                let map = e.get_mut();
-                if map.insert(op.method, op.operation).is_some() {
-                    // This will trow a warning if 2 routes have the same path and method
-                    // This is allowed by Rocket when a ranking is given for example: `#[get("/user", rank = 2)]`
-                    // See: https://rocket.rs/v0.4/guide/requests/#forwarding
-                    println!("Warning: Operation replaced for {}:{}", op.method, e.key());
+        self.servers.push(server);
+    }
+
+    /// Add a server to the `servers` of the generated output, if its variables are valid.
+    ///
+    /// Returns an error if a variable has an empty `enum`, or a `default` that is not in its
+    /// `enum`.
+    pub fn try_add_server(&mut self, server: Server) -> Result<()> {
+        validate_server(&server)?;
+        self.servers.push(server);
+        Ok(())
+    }
+
+    /// Set the `externalDocs` of the generated output, a link to more documentation.
+    ///
+    /// Returns an error if the `url` is empty.
+    pub fn set_external_docs(&mut self, url: &str, description: Option<String>) -> Result<()> {
+        self.external_docs = Some(external_docs(url, description)?);
+        Ok(())
+    }
+
+    /// Set the `servers` of an added operation, these override the `servers` of its path and
+    /// of the document, for example for an endpoint that is served by a different host.
+    ///
+    /// Returns an [`OpenApiError::MissingOperation`] error if no operation was added for the
+    /// path and method.
+    pub fn set_operation_servers(
+        &mut self,
+        path: &str,
+        method: Method,
+        servers: Vec<Server>,
+    ) -> Result<()> {
+        self.operation_mut(path, method)?.servers = Some(servers);
+        Ok(())
+    }
+
+    /// Set the `externalDocs` of an added operation, a link to more documentation.
+    ///
+    /// Returns an error if the `url` is empty, or an [`OpenApiError::MissingOperation`] error
+    /// if no operation was added for the path and method.
+    pub fn set_operation_external_docs(
+        &mut self,
+        path: &str,
+        method: Method,
+        url: &str,
+        description: Option<String>,
+    ) -> Result<()> {
+        let docs = external_docs(url, description)?;
+        self.operation_mut(path, method)?.external_docs = Some(docs);
+        Ok(())
+    }
+
+    /// Set the `servers` of the `PathItem` with the `path`, these override the `servers` of
+    /// the document for all the operations of the path, whatever their methods are.
+    /// Servers for a path without any operations are not added to the document.
+    pub fn set_path_servers(&mut self, path: &str, servers: Vec<Server>) {
+        self.path_servers
+            .insert(normalize_path(path, self.settings.trailing_slash), servers);
+    }
+
+    /// Set the `parameters` of the `PathItem` with the `path`, these apply to all the
+    /// operations of the path, like an `id` path parameter used by both `GET` and `DELETE`.
+    /// Parameters for a path without any operations are not added to the document.
+    pub fn set_path_parameters(&mut self, path: &str, params: Vec<RefOr<Parameter>>) {
+        self.path_parameters
+            .insert(normalize_path(path, self.settings.trailing_slash), params);
+    }
+
+    /// Move the parameters that are identical in all the operations of a path to the
+    /// `parameters` of its `PathItem`, and remove them from the operations. Only paths with at
+    /// least two operations are changed. Call this after all operations are added.
+    pub fn hoist_path_parameters(&mut self) {
+        for (path, map) in &mut self.operations {
+            if map.len() < 2 || map.contains_key(&Method::Connect) {
+                continue;
+            }
+            let first = map
+                .iter()
+                .min_by_key(|(method, _)| method_order(**method))
+                .map(|(_, op)| op)
+                .expect("The path has operations.");
+            let shared: Vec<RefOr<Parameter>> = first
+                .parameters
+                .iter()
+                .filter(|param| map.values().all(|op| op.parameters.contains(param)))
+                .cloned()
+                .collect();
+            if shared.is_empty() {
+                continue;
+            }
+            for op in map.values_mut() {
+                op.parameters.retain(|param| !shared.contains(param));
+            }
+            let params = self.path_parameters.entry(path.clone()).or_default();
+            for param in shared {
+                if !params.contains(&param) {
+                    params.push(param);
+                }
+            }
+        }
+    }
+
+    /// Add a vendor extension to the root of the generated output, like `x-tagGroups`.
+    /// An extension with the same key is replaced.
+    ///
+    /// Returns an error if the `key` does not start with `x-`.
+    pub fn add_root_extension(&mut self, key: &str, value: serde_json::Value) -> Result<()> {
+        check_extension_key(key)?;
+        self.extensions.insert(key.to_owned(), value);
+        Ok(())
+    }
+
+    /// Adds/Replace a webhook, a request the API may send to the client.
+    /// For OpenAPI 3.1 documents this is added to `webhooks`, for OpenAPI 3.0 documents
+    /// to the `x-webhooks` extension.
+    pub fn add_webhook(&mut self, name: &str, item: PathItem) {
+        self.webhooks.insert(name.to_owned(), item);
+    }
+
+    /// Add a new `HTTP Method` to the collection of endpoints in the `OpenApiGenerator`.
+    ///
+    /// If an operation was already added for the same path and method,
+    /// [`OpenApiSettings::on_collision`] decides what happens. By default the operation is
+    /// replaced and a warning is logged. This is allowed by Rocket when a ranking is given,
+    /// for example: `#[get("/user", rank = 2)]`.
+    /// Use [`try_add_operation`](Self::try_add_operation) to handle this case yourself.
+    /// Other errors are added to the [`warnings`](Self::warnings).
+    pub fn add_operation(&mut self, op: OperationInfo) {
+        let path = normalize_path(&op.path, self.settings.trailing_slash);
+        let method = op.method;
+        let mut operation = op.operation;
+        self.normalize_operation_id(&mut operation);
+        match self.add_normalized_operation(path.clone(), method, operation.clone()) {
+            Ok(()) => {}
+            Err(err @ OpenApiError::DuplicateOperation { .. }) => {
+                // This happens if 2 routes have the same path and method
+                // See: https://rocket.rs/v0.4/guide/requests/#forwarding
+                match self.settings.on_collision {
+                    CollisionPolicy::Panic => panic!("{}", err),
+                    CollisionPolicy::KeepFirst => {
+                        log::warn!("Operation for {} {} not replaced.", method, path);
+                    }
+                    CollisionPolicy::KeepLast => {
+                        log::warn!("Operation replaced for {} {}.", method, path);
+                        self.insert_operation(path, method, operation);
+                    }
+                    CollisionPolicy::Error => self.add_warning(OpenApiWarning::OperationError(err)),
                 }
             }
+            Err(err) => self.add_warning(OpenApiWarning::OperationError(err)),
+        }
+    }
+
+    /// Add a new `HTTP Method` to the collection of endpoints in the `OpenApiGenerator`.
+    ///
+    /// Returns an [`OpenApiError::DuplicateOperation`] error if an operation was already added
+    /// for the same path and method. If [`OpenApiSettings::ignore_identical_operations`] is
+    /// enabled, adding an operation identical to the existing one is a no-op.
+    ///
+    /// Operations for `Method::Connect` are handled according to
+    /// [`OpenApiSettings::connect_handling`].
+    pub fn try_add_operation(&mut self, op: OperationInfo) -> Result<()> {
+        let OperationInfo {
+            path,
+            method,
+            mut operation,
+        } = op;
+        let path = normalize_path(&path, self.settings.trailing_slash);
+        self.normalize_operation_id(&mut operation);
+        self.add_normalized_operation(path, method, operation)
+    }
+
+    /// Change the `operation_id` using [`OpenApiSettings::operation_id_fn`].
+    fn normalize_operation_id(&self, operation: &mut Operation) {
+        if let Some(op_id) = &operation.operation_id {
+            let op_id = match &self.settings.operation_id_fn {
+                Some(operation_id_fn) => operation_id_fn(op_id),
+                None => op_id.trim_start_matches(':').replace("::", "_"),
+            };
+            operation.operation_id = Some(op_id);
+        }
+    }
+
+    fn add_normalized_operation(
+        &mut self,
+        path: String,
+        method: Method,
+        operation: Operation,
+    ) -> Result<()> {
+        if !self.handle_connect(&path, method)? {
+            return Ok(());
+        }
+        if let Some(existing) = self.operations.get(&path).and_then(|map| map.get(&method)) {
+            if self.settings.ignore_identical_operations && *existing == operation {
+                return Ok(());
+            }
+            return Err(OpenApiError::DuplicateOperation { path, method });
+        }
+        self.insert_operation(path, method, operation);
+        Ok(())
+    }
+
+    /// Apply [`OpenApiSettings::connect_handling`] to an operation that is added. Returns
+    /// `Ok(false)` when the operation is dropped.
+    fn handle_connect(&mut self, path: &str, method: Method) -> Result<bool> {
+        if method != Method::Connect {
+            return Ok(true);
+        }
+        match self.settings.connect_handling {
+            ConnectHandling::Drop => {
+                self.add_warning(OpenApiWarning::DroppedConnect {
+                    path: path.to_owned(),
+                });
+                Ok(false)
+            }
+            ConnectHandling::Extension => Ok(true),
+            ConnectHandling::Error => Err(OpenApiError::UnsupportedMethod {
+                path: path.to_owned(),
+                method,
+            }),
+        }
+    }
+
+    fn insert_operation(
+        &mut self,
+        path: String,
+        method: Method,
+        operation: Operation,
+    ) -> Option<Operation> {
+        match self.operations.entry(path) {
+            MapEntry::Occupied(mut e) => e.get_mut().insert(method, operation),
             MapEntry::Vacant(e) => {
                 let mut map = HashMap::new();
-                map.insert(op.method, op.operation);
+                map.insert(method, operation);
                 e.insert(map);
+                None
+            }
+        }
+    }
+
+    /// Add or replace the operation for the path and method of `op`, for example to override
+    /// a generated operation with a hand written one.
+    /// Returns the operation that was replaced, if any.
+    ///
+    /// Operations for `Method::Connect` are handled according to
+    /// [`OpenApiSettings::connect_handling`], with an [`OpenApiWarning::OperationError`] warning
+    /// instead of an error for `ConnectHandling::Error`.
+    pub fn replace_operation(&mut self, op: OperationInfo) -> Option<Operation> {
+        let OperationInfo {
+            path,
+            method,
+            mut operation,
+        } = op;
+        let path = normalize_path(&path, self.settings.trailing_slash);
+        match self.handle_connect(&path, method) {
+            Ok(true) => {}
+            Ok(false) => return None,
+            Err(err) => {
+                self.add_warning(OpenApiWarning::OperationError(err));
+                return None;
+            }
+        }
+        self.normalize_operation_id(&mut operation);
+        self.insert_operation(path, method, operation)
+    }
+
+    /// Remove the operation that was added for the path and method.
+    /// Returns the removed operation, if any.
+    pub fn remove_operation(&mut self, path: &str, method: Method) -> Option<Operation> {
+        let path = normalize_path(path, self.settings.trailing_slash);
+        let map = self.operations.get_mut(&path)?;
+        let removed = map.remove(&method);
+        if map.is_empty() {
+            self.operations.remove(&path);
+        }
+        removed
+    }
+
+    /// Mark the operation as deprecated. When a `sunset` date is given it is added to the
+    /// `x-sunset` extension of the operation, for example `2023-01-01`.
+    ///
+    /// Returns an [`OpenApiError::MissingOperation`] error if no operation was added for the
+    /// path and method.
+    pub fn mark_deprecated(
+        &mut self,
+        path: &str,
+        method: Method,
+        sunset: Option<String>,
+    ) -> Result<()> {
+        let operation = self.operation_mut(path, method)?;
+        operation.deprecated = true;
+        if let Some(sunset) = sunset {
+            operation
+                .extensions
+                .insert("x-sunset".to_owned(), sunset.into());
+        }
+        Ok(())
+    }
+
+    /// Add a vendor extension to an added operation, like `x-amazon-apigateway-integration`.
+    /// An extension with the same key is replaced.
+    ///
+    /// Returns an error if the `key` does not start with `x-`, or an
+    /// [`OpenApiError::MissingOperation`] error if no operation was added for the path and method.
+    pub fn add_operation_extension(
+        &mut self,
+        path: &str,
+        method: Method,
+        key: &str,
+        value: serde_json::Value,
+    ) -> Result<()> {
+        check_extension_key(key)?;
+        self.operation_mut(path, method)?
+            .extensions
+            .insert(key.to_owned(), value);
+        Ok(())
+    }
+
+    /// Get a copy of an added operation where all the `$ref`s to schemas are replaced with the
+    /// schema itself. This creates an operation that does not depend on `components`.
+    ///
+    /// Recursive types can not be fully inlined, the reference to the type is kept
+    /// when it is used inside itself.
+    /// Returns an [`OpenApiError::MissingOperation`] error if no operation was added for the
+    /// path and method.
+    pub fn inline_operation_schemas(&self, path: &str, method: Method) -> Result<Operation> {
+        let mut operation = self
+            .operations
+            .get(&normalize_path(path, self.settings.trailing_slash))
+            .and_then(|map| map.get(&method))
+            .cloned()
+            .ok_or_else(|| OpenApiError::MissingOperation {
+                path: path.to_owned(),
+                method,
+            })?;
+        let mut visitor = InlineReferences::new(
+            self.schema_generator.definitions(),
+            self.settings.schema_settings.definitions_path.clone(),
+        );
+        visit_operation(&mut visitor, &mut operation);
+        Ok(operation)
+    }
+
+    /// Set the request body of an added operation, this replaces the existing request body.
+    ///
+    /// Returns an [`OpenApiError::MissingOperation`] error if no operation was added for the
+    /// path and method.
+    pub fn set_request_body(
+        &mut self,
+        path: &str,
+        method: Method,
+        body: RequestBody,
+    ) -> Result<()> {
+        self.operation_mut(path, method)?.request_body = Some(body.into());
+        Ok(())
+    }
+
+    /// Set the request body of an added operation to an `application/json` body with the schema
+    /// of `T`, this replaces the existing request body.
+    ///
+    /// Returns an [`OpenApiError::MissingOperation`] error if no operation was added for the
+    /// path and method.
+    pub fn json_request_body<T: ?Sized + JsonSchema>(
+        &mut self,
+        path: &str,
+        method: Method,
+        required: bool,
+        description: Option<String>,
+    ) -> Result<()> {
+        // Check the operation first, so no schema is added for a missing operation.
+        self.operation_mut(path, method)?;
+        let mut content = Map::new();
+        content.insert(
+            "application/json".to_owned(),
+            MediaType {
+                schema: Some(self.json_schema::<T>()),
+                ..MediaType::default()
+            },
+        );
+        let body = RequestBody {
+            description,
+            content,
+            required,
+            ..RequestBody::default()
+        };
+        self.set_request_body(path, method, body)
+    }
+
+    /// Add a content type with its schema to the request body of an added operation, for
+    /// example `application/x-www-form-urlencoded`. The request body is created if it does not
+    /// exist yet. If the content type was already added both schemas are accepted.
+    ///
+    /// Returns an error if the operation does not exist or the request body is a reference.
+    pub fn add_request_content(
+        &mut self,
+        path: &str,
+        method: Method,
+        content_type: &str,
+        schema: SchemaObject,
+    ) -> Result<()> {
+        let operation = self.operation_mut(path, method)?;
+        let request_body = operation
+            .request_body
+            .get_or_insert_with(|| RequestBody::default().into());
+        let request_body = match request_body {
+            RefOr::Object(request_body) => request_body,
+            RefOr::Ref(_) => {
+                return Err(OpenApiError::new(format!(
+                    "The request body of `{} {}` is a reference.",
+                    method, path
+                )))
             }
         };
+        let media = MediaType {
+            schema: Some(schema),
+            ..MediaType::default()
+        };
+        add_media_type(&mut request_body.content, content_type, media);
+        Ok(())
     }
 
-    /// Returns a JSON Schema object for the type `T`.
-    pub fn json_schema<T: ?Sized + JsonSchema>(&mut self) -> SchemaObject {
-        self.schema_generator.subschema_for::<T>().into()
+    /// Set the `multipart/form-data` content of the request body of an added operation. The
+    /// schema is an object with a property for every part, the `content_type` and `headers` of
+    /// the parts are added to the `encoding`. The request body is created if it does not exist
+    /// yet and is required when one of the parts is required.
+    ///
+    /// Returns an error if the operation does not exist or the request body is a reference.
+    pub fn add_multipart_body(
+        &mut self,
+        path: &str,
+        method: Method,
+        parts: Vec<MultipartPart>,
+    ) -> Result<()> {
+        let mut schema = SchemaObject {
+            instance_type: Some(InstanceType::Object.into()),
+            ..SchemaObject::default()
+        };
+        let mut encoding = Map::new();
+        let mut required = false;
+        for part in parts {
+            let object = schema.object();
+            object
+                .properties
+                .insert(part.name.clone(), part.schema.into());
+            if part.required {
+                object.required.insert(part.name.clone());
+                required = true;
+            }
+            if part.content_type.is_some() || !part.headers.is_empty() {
+                let part_encoding = Encoding {
+                    content_type: part.content_type,
+                    headers: part.headers,
+                    ..Encoding::default()
+                };
+                encoding.insert(part.name, part_encoding);
+            }
+        }
+        let operation = self.operation_mut(path, method)?;
+        let request_body = match operation
+            .request_body
+            .get_or_insert_with(|| RequestBody::default().into())
+        {
+            RefOr::Object(request_body) => request_body,
+            RefOr::Ref(_) => {
+                return Err(OpenApiError::new(format!(
+                    "The request body of `{} {}` is a reference.",
+                    method, path
+                )))
+            }
+        };
+        request_body.required |= required;
+        let media = MediaType {
+            schema: Some(schema),
+            encoding,
+            ..MediaType::default()
+        };
+        request_body
+            .content
+            .insert("multipart/form-data".to_owned(), media);
+        Ok(())
     }
 
-    /// Obtain the internal `SchemaGenerator` object.
-    #[must_use]
-    pub fn schema_generator(&self) -> &SchemaGenerator {
-        &self.schema_generator
+    /// Add a content type for raw bytes, like `application/octet-stream`, to the request body of
+    /// an added operation, for example for file uploads. The schema is a `string` with the
+    /// `binary` format. The request body is created if it does not exist yet.
+    ///
+    /// Returns an error if the operation does not exist or the request body is a reference.
+    pub fn add_binary_request_body(
+        &mut self,
+        path: &str,
+        method: Method,
+        content_type: &str,
+        required: bool,
+    ) -> Result<()> {
+        self.add_request_content(path, method, content_type, binary_schema())?;
+        if let Some(RefOr::Object(request_body)) =
+            &mut self.operation_mut(path, method)?.request_body
+        {
+            request_body.required = required;
+        }
+        Ok(())
     }
 
-    /// Return the component definition/schema of an object without any references.
-    pub fn json_schema_no_ref<T: ?Sized + JsonSchema>(&mut self) -> SchemaObject {
-        <T>::json_schema(&mut self.schema_generator).into()
+    /// Add a content type for raw bytes, like `application/octet-stream`, to the response with
+    /// the `status` code of an added operation. The schema is a `string` with the `binary`
+    /// format. The response is created if it does not exist yet.
+    ///
+    /// Returns an error if the operation does not exist or the response is a reference.
+    pub fn add_binary_response(
+        &mut self,
+        path: &str,
+        method: Method,
+        status: u16,
+        content_type: &str,
+    ) -> Result<()> {
+        self.add_response_content(path, method, status, content_type, binary_schema())
     }
 
-    /// Generate an `OpenApi` specification for all added operations.
-    #[must_use]
-    pub fn into_openapi(self) -> OpenApi {
-        let mut schema_generator = self.schema_generator;
-        let mut schemas = schema_generator.take_definitions();
+    /// Add a content type with its schema to the response with the `status` code of an added
+    /// operation, for example `text/csv`. The response is created if it does not exist yet.
+    /// If the content type was already added both schemas are accepted.
+    ///
+    /// Returns an error if the operation does not exist or the response is a reference.
+    pub fn add_response_content(
+        &mut self,
+        path: &str,
+        method: Method,
+        status: u16,
+        content_type: &str,
+        schema: SchemaObject,
+    ) -> Result<()> {
+        let operation = self.operation_mut(path, method)?;
+        add_schema_response(&mut operation.responses, status, content_type, schema)
+    }
 
-        // Add the security schemes
-        let mut schemes: Map<String, RefOr<SecurityScheme>> = Default::default();
-        for (name, schema) in self.security_schemes {
-            schemes.insert(name, schema.into());
+    /// Document the `200` response of an added operation as a stream of server-sent events,
+    /// where the `data` of each event has the `event_schema`. This sets the `text/event-stream`
+    /// content of the response (keeping the content type of `EventStream`, which includes a
+    /// `charset`) and adds an `x-sse: true` extension to it as a hint for tooling.
+    ///
+    /// Returns an error if the operation does not exist or the response is a reference.
+    pub fn add_sse_response(
+        &mut self,
+        path: &str,
+        method: Method,
+        event_schema: RefOr<SchemaObject>,
+    ) -> Result<()> {
+        let schema = match event_schema {
+            RefOr::Ref(reference) => SchemaObject::new_ref(reference.reference),
+            RefOr::Object(schema) => schema,
+        };
+        let operation = self.operation_mut(path, method)?;
+        let content = match ensure_status_code_exists(&mut operation.responses, 200) {
+            RefOr::Object(response) => &mut response.content,
+            RefOr::Ref(_) => {
+                return Err(OpenApiError::new(
+                    "Altering Ref responses is not supported.".to_owned(),
+                ))
+            }
+        };
+        let content_type = content
+            .keys()
+            .find(|content_type| content_type.starts_with("text/event-stream"))
+            .cloned()
+            .unwrap_or_else(|| "text/event-stream".to_owned());
+        let mut extensions = Object::default();
+        extensions.insert("x-sse".to_owned(), serde_json::Value::Bool(true));
+        content.insert(
+            content_type,
+            MediaType {
+                schema: Some(schema),
+                extensions,
+                ..MediaType::default()
+            },
+        );
+        Ok(())
+    }
+
+    /// Add an `example` to all the content of the response with the `status` code of an added
+    /// operation. The `value` is serialized as JSON.
+    ///
+    /// In debug builds a warning is added if the example does not match the schema of the content.
+    /// Returns an error if the operation or response does not exist, or the value could not be
+    /// serialized.
+    pub fn add_response_example<T: Serialize>(
+        &mut self,
+        path: &str,
+        method: Method,
+        status: u16,
+        value: &T,
+    ) -> Result<()> {
+        let example = to_example(value)?;
+        let operation = find_operation_mut(
+            &mut self.operations,
+            path,
+            method,
+            self.settings.trailing_slash,
+        )?;
+        let content = match operation.responses.responses.get_mut(&status.to_string()) {
+            Some(RefOr::Object(response)) => &mut response.content,
+            _ => {
+                return Err(OpenApiError::new(format!(
+                    "No response with status `{}` for `{} {}`.",
+                    status, method, path
+                )))
+            }
+        };
+        let invalid = set_example(content, example, &self.schema_generator, &self.settings);
+        for content_type in invalid {
+            self.add_warning(OpenApiWarning::InvalidExample {
+                path: path.to_owned(),
+                method,
+                content_type,
+            });
         }
+        Ok(())
+    }
 
-        for visitor in schema_generator.visitors_mut() {
-            for schema in schemas.values_mut() {
-                visitor.visit_schema(schema)
+    /// Add an `application/json` response with the schema of `T` and the `description` to an
+    /// added operation, for example to document both a `201` and a `202` response. The other
+    /// responses of the operation are kept.
+    ///
+    /// Returns an error if the operation does not exist or the response is a reference.
+    pub fn add_json_response<T: JsonSchema>(
+        &mut self,
+        path: &str,
+        method: Method,
+        status: u16,
+        description: &str,
+    ) -> Result<()> {
+        let schema = self.json_schema::<T>();
+        let responses = &mut self.operation_mut(path, method)?.responses;
+        add_schema_response(responses, status, "application/json", schema)?;
+        if let RefOr::Object(response) = ensure_status_code_exists(responses, status) {
+            response.description = description.to_owned();
+        }
+        Ok(())
+    }
+
+    /// Add an `application/problem+json` ([RFC 7807](https://datatracker.ietf.org/doc/html/rfc7807))
+    /// content with the schema of `T` to the response with the `status` code of an added
+    /// operation. Other content types of the response, like `application/json`, are kept.
+    ///
+    /// Returns an error if the operation does not exist or the response is a reference.
+    pub fn add_problem_json_response<T: JsonSchema>(
+        &mut self,
+        path: &str,
+        method: Method,
+        status: u16,
+    ) -> Result<()> {
+        // Check the operation first, so no schema is added for a missing operation.
+        self.operation_mut(path, method)?;
+        let schema = self.json_schema::<T>();
+        self.add_response_content(path, method, status, "application/problem+json", schema)
+    }
+
+    /// Set the `encoding` of the content type of the response with the `status` code of an
+    /// added operation, this replaces the existing `encoding`. The response and content type
+    /// are created if they do not exist yet.
+    ///
+    /// Returns an error if the operation does not exist or the response is a reference.
+    pub fn set_media_type_encoding(
+        &mut self,
+        path: &str,
+        method: Method,
+        status: u16,
+        content_type: &str,
+        encoding: Map<String, Encoding>,
+    ) -> Result<()> {
+        let operation = self.operation_mut(path, method)?;
+        match ensure_status_code_exists(&mut operation.responses, status) {
+            RefOr::Object(response) => {
+                response
+                    .content
+                    .entry(content_type.to_owned())
+                    .or_default()
+                    .encoding = encoding;
+                Ok(())
             }
+            RefOr::Ref(_) => Err(OpenApiError::new(format!(
+                "The response `{}` of `{} {}` is a reference.",
+                status, method, path
+            ))),
         }
+    }
 
-        OpenApi {
-            openapi: "3.0.0".to_owned(),
-            paths: {
-                let mut paths = Map::new();
-                for (path, map) in self.operations {
-                    for (method, op) in map {
-                        let path_item = paths.entry(path.clone()).or_default();
-                        set_operation(path_item, method, op);
+    /// Add a named example to the `examples` of the content type of the response with the
+    /// `status` code of an added operation. The response and content type are created if they
+    /// do not exist yet. An example with the same name is replaced.
+    ///
+    /// Returns an error if the operation does not exist or the response is a reference.
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_named_example(
+        &mut self,
+        path: &str,
+        method: Method,
+        status: u16,
+        content_type: &str,
+        name: &str,
+        example: Example,
+    ) -> Result<()> {
+        let operation = self.operation_mut(path, method)?;
+        let mut examples = Map::new();
+        examples.insert(name.to_owned(), example);
+        let media = MediaType {
+            examples: Some(examples),
+            ..MediaType::default()
+        };
+        add_content_response(&mut operation.responses, status, content_type, media)
+    }
+
+    /// Add an `example` to all the content of the request body of an added operation.
+    /// The `value` is serialized as JSON.
+    ///
+    /// In debug builds a warning is added if the example does not match the schema of the content.
+    /// Returns an error if the operation or request body does not exist, or the value could not be
+    /// serialized.
+    pub fn add_request_example<T: Serialize>(
+        &mut self,
+        path: &str,
+        method: Method,
+        value: &T,
+    ) -> Result<()> {
+        let example = to_example(value)?;
+        let operation = find_operation_mut(
+            &mut self.operations,
+            path,
+            method,
+            self.settings.trailing_slash,
+        )?;
+        let content = match &mut operation.request_body {
+            Some(RefOr::Object(request_body)) => &mut request_body.content,
+            _ => {
+                return Err(OpenApiError::new(format!(
+                    "No request body for `{} {}`.",
+                    method, path
+                )))
+            }
+        };
+        let invalid = set_example(content, example, &self.schema_generator, &self.settings);
+        for content_type in invalid {
+            self.add_warning(OpenApiWarning::InvalidExample {
+                path: path.to_owned(),
+                method,
+                content_type,
+            });
+        }
+        Ok(())
+    }
+
+    /// Set the `description` and/or `example` of a parameter of an added operation.
+    /// Values that are `None` are left unchanged.
+    ///
+    /// Returns an [`OpenApiError::MissingParameter`] error if the operation has no parameter
+    /// with the name, or an [`OpenApiError::MissingOperation`] error if no operation was added.
+    pub fn describe_parameter(
+        &mut self,
+        path: &str,
+        method: Method,
+        param_name: &str,
+        description: Option<String>,
+        example: Option<serde_json::Value>,
+    ) -> Result<()> {
+        let parameter = self.parameter_mut(path, method, param_name)?;
+        if let Some(description) = description {
+            parameter.description = Some(description);
+        }
+        if let Some(example) = example {
+            match &mut parameter.value {
+                ParameterValue::Schema {
+                    example: parameter_example,
+                    ..
+                } => *parameter_example = Some(example),
+                ParameterValue::Content { content } => {
+                    for media_type in content.values_mut() {
+                        media_type.example = Some(example.clone());
                     }
                 }
-                paths
+            }
+        }
+        Ok(())
+    }
+
+    /// Create a `Header` with the schema of `T`, to use with
+    /// [`add_header_parameter`](Self::add_header_parameter) or
+    /// [`add_response_header`](Self::add_response_header).
+    pub fn header_for<T: ?Sized + JsonSchema>(
+        &mut self,
+        description: Option<String>,
+        required: bool,
+    ) -> Header {
+        Header {
+            description,
+            required,
+            deprecated: false,
+            allow_empty_value: false,
+            value: ParameterValue::Schema {
+                style: None,
+                explode: None,
+                allow_reserved: false,
+                schema: self.json_schema::<T>(),
+                example: None,
+                examples: None,
             },
-            components: Some(Components {
-                schemas: schemas.into_iter().map(|(k, v)| (k, v.into())).collect(),
-                security_schemes: schemes,
-                ..Default::default()
-            }),
-            ..OpenApi::default()
+            extensions: Object::default(),
         }
     }
-}
 
-fn set_operation(path_item: &mut PathItem, method: Method, op: Operation) {
-    use Method::{Connect, Delete, Get, Head, Options, Patch, Post, Put, Trace};
-    let option = match method {
-        Get => &mut path_item.get,
-        Put => &mut path_item.put,
-        Post => &mut path_item.post,
-        Delete => &mut path_item.delete,
-        Options => &mut path_item.options,
-        Head => &mut path_item.head,
-        Patch => &mut path_item.patch,
-        Trace => &mut path_item.trace,
-        // Connect not available in OpenAPI3. Maybe should set in extensions?
-        Connect => return,
-    };
-    assert!(option.is_none());
-    option.replace(op);
+    /// Add a request header to the parameters of an added operation, as a parameter with
+    /// `in: header`, for example `X-Request-Id`.
+    ///
+    /// Returns an [`OpenApiError::MissingOperation`] error if no operation was added for the
+    /// path and method.
+    pub fn add_header_parameter(
+        &mut self,
+        path: &str,
+        method: Method,
+        name: &str,
+        header: Header,
+    ) -> Result<()> {
+        let parameter = Parameter {
+            name: name.to_owned(),
+            location: "header".to_owned(),
+            description: header.description,
+            required: header.required,
+            deprecated: header.deprecated,
+            allow_empty_value: header.allow_empty_value,
+            value: header.value,
+            extensions: header.extensions,
+        };
+        self.operation_mut(path, method)?
+            .parameters
+            .push(parameter.into());
+        Ok(())
+    }
+
+    /// Add or replace a header of the response with the `status` code of an added operation,
+    /// for example `X-RateLimit-Remaining`. The response is created if it does not exist yet.
+    ///
+    /// Returns an error if the operation does not exist or the response is a reference.
+    pub fn add_response_header(
+        &mut self,
+        path: &str,
+        method: Method,
+        status: u16,
+        name: &str,
+        header: Header,
+    ) -> Result<()> {
+        let operation = self.operation_mut(path, method)?;
+        match ensure_status_code_exists(&mut operation.responses, status) {
+            RefOr::Object(response) => {
+                response.headers.insert(name.to_owned(), header.into());
+                Ok(())
+            }
+            RefOr::Ref(_) => Err(OpenApiError::new(format!(
+                "The response `{}` of `{} {}` is a reference.",
+                status, method, path
+            ))),
+        }
+    }
+
+    /// Add or replace a link of the response with the `status` code of an added operation.
+    /// A link describes how a value of the response can be used in another operation, for
+    /// example the `id` of a created order in `GET /orders/{id}`.
+    /// The response is created if it does not exist yet.
+    ///
+    /// A warning is added by [`into_openapi_with_warnings`](Self::into_openapi_with_warnings)
+    /// if the `operation_id` of the link does not exist.
+    /// Returns an error if the operation does not exist or the response is a reference.
+    pub fn add_response_link(
+        &mut self,
+        path: &str,
+        method: Method,
+        status: u16,
+        name: &str,
+        link: Link,
+    ) -> Result<()> {
+        let operation = self.operation_mut(path, method)?;
+        match ensure_status_code_exists(&mut operation.responses, status) {
+            RefOr::Object(response) => {
+                response.links.insert(name.to_owned(), link.into());
+                Ok(())
+            }
+            RefOr::Ref(_) => Err(OpenApiError::new(format!(
+                "The response `{}` of `{} {}` is a reference.",
+                status, method, path
+            ))),
+        }
+    }
+
+    /// Set how the value of a parameter of an added operation is serialized, for example
+    /// `style: form, explode: true` for `?tags=a&tags=b` or `style: form, explode: false` for
+    /// `?tags=a,b`.
+    ///
+    /// Returns an error if the style can not be used for the location of the parameter
+    /// (`spaceDelimited`, `pipeDelimited` and `deepObject` are only valid for query parameters),
+    /// or if the parameter is described using `content` instead of a schema.
+    /// Returns an [`OpenApiError::MissingParameter`] error if the operation has no parameter
+    /// with the name, or an [`OpenApiError::MissingOperation`] error if no operation was added.
+    pub fn set_parameter_serialization(
+        &mut self,
+        path: &str,
+        method: Method,
+        param_name: &str,
+        style: ParameterStyle,
+        explode: bool,
+    ) -> Result<()> {
+        let parameter = self.parameter_mut(path, method, param_name)?;
+        let allowed: &[&str] = match style {
+            ParameterStyle::Matrix | ParameterStyle::Label => &["path"],
+            ParameterStyle::Form => &["query", "cookie"],
+            ParameterStyle::Simple => &["path", "header"],
+            ParameterStyle::SpaceDelimited
+            | ParameterStyle::PipeDelimited
+            | ParameterStyle::DeepObject => &["query"],
+        };
+        if !allowed.contains(&parameter.location.as_str()) {
+            return Err(OpenApiError::new(format!(
+                "The style `{:?}` can not be used for the {} parameter `{}` of `{} {}`.",
+                style, parameter.location, param_name, method, path
+            )));
+        }
+        match &mut parameter.value {
+            ParameterValue::Schema {
+                style: parameter_style,
+                explode: parameter_explode,
+                ..
+            } => {
+                *parameter_style = Some(style);
+                *parameter_explode = Some(explode);
+                Ok(())
+            }
+            ParameterValue::Content { .. } => Err(OpenApiError::new(format!(
+                "The parameter `{}` of `{} {}` uses `content`, it has no style.",
+                param_name, method, path
+            ))),
+        }
+    }
+
+    /// Add a named example to the `examples` of a parameter of an added operation, an example
+    /// with the same name is replaced.
+    /// A parameter should not have both an `example` and `examples`, when the parameter already
+    /// has an `example` a warning is added.
+    ///
+    /// Returns an error if the parameter does not exist or uses `content`.
+    pub fn add_parameter_example(
+        &mut self,
+        path: &str,
+        method: Method,
+        param_name: &str,
+        name: &str,
+        example: Example,
+    ) -> Result<()> {
+        let parameter = self.parameter_mut(path, method, param_name)?;
+        let has_example = match &mut parameter.value {
+            ParameterValue::Schema {
+                example: single,
+                examples,
+                ..
+            } => {
+                examples
+                    .get_or_insert_with(Map::new)
+                    .insert(name.to_owned(), example);
+                single.is_some()
+            }
+            ParameterValue::Content { .. } => {
+                return Err(OpenApiError::new(format!(
+                    "The parameter `{}` of `{} {}` uses `content`, add the example to the content.",
+                    param_name, method, path
+                )))
+            }
+        };
+        if has_example {
+            self.add_warning(OpenApiWarning::InvalidOperation(OpenApiError::new(
+                format!(
+                    "The parameter `{}` of `{} {}` has both an `example` and `examples`.",
+                    param_name, method, path
+                ),
+            )));
+        }
+        Ok(())
+    }
+
+    fn parameter_mut(&mut self, path: &str, method: Method, name: &str) -> Result<&mut Parameter> {
+        self.operation_mut(path, method)?
+            .parameters
+            .iter_mut()
+            .find_map(|parameter| match parameter {
+                RefOr::Object(parameter) if parameter.name == name => Some(parameter),
+                _ => None,
+            })
+            .ok_or_else(|| OpenApiError::MissingParameter {
+                path: path.to_owned(),
+                method,
+                name: name.to_owned(),
+            })
+    }
+
+    fn operation_mut(&mut self, path: &str, method: Method) -> Result<&mut Operation> {
+        find_operation_mut(
+            &mut self.operations,
+            path,
+            method,
+            self.settings.trailing_slash,
+        )
+    }
+
+    fn add_warning(&mut self, warning: OpenApiWarning) {
+        log::warn!("{}", warning);
+        self.warnings.push(warning);
+    }
+
+    /// Get the warnings that were collected so far.
+    #[must_use]
+    pub fn warnings(&self) -> &[OpenApiWarning] {
+        &self.warnings
+    }
+
+    /// Take the warnings that were collected so far.
+    pub fn take_warnings(&mut self) -> Vec<OpenApiWarning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// Returns a JSON Schema object for the type `T`.
+    ///
+    /// When a different type with the same schema name was used before, schemars adds a number
+    /// to the name of the second one, like `Config2`. The schema of `T` is named with
+    /// [`OpenApiSettings::schema_naming`] when it is added to the definitions.
+    pub fn json_schema<T: ?Sized + JsonSchema>(&mut self) -> SchemaObject {
+        let before = self.definition_names();
+        let schema = self.schema_generator.subschema_for::<T>();
+        self.name_schemas(before.as_ref(), vec![(T::schema_id(), schema)])
+            .remove(0)
+    }
+
+    /// The names of the definitions, when [`OpenApiSettings::schema_naming`] needs to know
+    /// which definitions are added by generating a schema.
+    fn definition_names(&self) -> Option<HashSet<String>> {
+        match self.settings.schema_naming {
+            SchemaNaming::TypeName => None,
+            _ => Some(
+                self.schema_generator
+                    .definitions()
+                    .keys()
+                    .cloned()
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Name the definitions that were added since `before` with
+    /// [`OpenApiSettings::schema_naming`]. Only the definitions of the generated `schemas`,
+    /// with their schema ids, are renamed, the other types keep the name from schemars. The
+    /// name from schemars is also kept when the new name is already used. When a type that was
+    /// renamed before is generated again under its schemars name, that copy is removed.
+    /// The references in the definitions and the `schemas` are updated to match.
+    fn name_schemas(
+        &mut self,
+        before: Option<&HashSet<String>>,
+        schemas: Vec<(Cow<'static, str>, Schema)>,
+    ) -> Vec<SchemaObject> {
+        let before = match before {
+            Some(before) => before,
+            None => {
+                return schemas
+                    .into_iter()
+                    .map(|(_, schema)| schema.into())
+                    .collect()
+            }
+        };
+        let prefix = self.settings.schema_settings.definitions_path.clone();
+        let mut renames: Map<String, String> = self
+            .schema_generator
+            .definitions()
+            .keys()
+            .filter(|name| !before.contains(*name))
+            .filter_map(|name| Some((name.clone(), self.schema_keys.get(name)?.clone())))
+            .collect();
+        for (schema_id, schema) in &schemas {
+            let name = match reference_name(schema, &prefix) {
+                Some(name)
+                    if !before.contains(name)
+                        && !renames.contains_key(name)
+                        && self.schema_generator.definitions().contains_key(name) =>
+                {
+                    name
+                }
+                _ => continue,
+            };
+            let key = match self.settings.schema_naming.schema_key(schema_id) {
+                Some(key) if key != name => key,
+                _ => continue,
+            };
+            if !self.schema_generator.definitions().contains_key(&key)
+                && !renames.values().any(|new| *new == key)
+            {
+                self.schema_keys.insert(name.to_owned(), key.clone());
+                renames.insert(name.to_owned(), key);
+            }
+        }
+        let mut schemas: Vec<SchemaObject> = schemas
+            .into_iter()
+            .map(|(_, schema)| schema.into())
+            .collect();
+        if renames.is_empty() {
+            return schemas;
+        }
+        let definitions = self.schema_generator.take_definitions();
+        let generated_again: HashSet<String> = renames
+            .iter()
+            .filter(|(_, new)| definitions.contains_key(*new))
+            .map(|(old, _)| old.clone())
+            .collect();
+        *self.schema_generator.definitions_mut() = definitions
+            .into_iter()
+            .filter(|(name, _)| !generated_again.contains(name))
+            .map(|(name, schema)| match renames.get(&name) {
+                Some(new) => (new.clone(), schema),
+                None => (name, schema),
+            })
+            .collect();
+        let mut references = ReplaceReferences {
+            references: renames
+                .iter()
+                .map(|(old, new)| (format!("{}{}", prefix, old), format!("{}{}", prefix, new)))
+                .collect(),
+        };
+        for schema in self.schema_generator.definitions_mut().values_mut() {
+            references.visit_schema(schema);
+        }
+        for schema in &mut schemas {
+            references.visit_schema_object(schema);
+        }
+        schemas
+    }
+
+    /// Generate the schemas of many independent types on multiple threads, like calling
+    /// [`json_schema`](Self::json_schema) for each of them, for example
+    /// `gen.json_schemas_parallel(vec![ParallelSchema::of::<User>(), ParallelSchema::of::<Pet>()])`.
+    ///
+    /// The schemas are returned, and their definitions added, in the order of `types`, so the
+    /// output is the same as generating them one after the other. The names of the schemas
+    /// still have to be assigned by this generator, so the types are generated on the current
+    /// thread instead when the name of a type is not the same as on the other thread. This is
+    /// the case when two different types end up with the same schema name, or when a type
+    /// uses a type that is not in `types` and was not generated before. So include the types
+    /// that are used by the fields in `types` too. Types with the same name and structurally
+    /// identical schemas share one definition.
+    pub fn json_schemas_parallel<I>(&mut self, types: I) -> Vec<SchemaObject>
+    where
+        I: IntoIterator<Item = ParallelSchema>,
+    {
+        let threads = std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get);
+        self.json_schemas_on_threads(types.into_iter().collect(), threads)
+            .0
+    }
+
+    /// Returns the schemas, and `true` when the schemas from the other threads were used.
+    fn json_schemas_on_threads(
+        &mut self,
+        types: Vec<ParallelSchema>,
+        threads: usize,
+    ) -> (Vec<SchemaObject>, bool) {
+        let before = self.definition_names();
+        let threads = threads.clamp(1, types.len().max(1));
+        let chunk_size = types.len().div_ceil(threads).max(1);
+        // `SchemaSettings` can not be sent to other threads because of its visitors, so all the
+        // other settings are copied. The visitors are applied to all definitions in
+        // `into_openapi` anyway.
+        let settings = &self.settings.schema_settings;
+        let (option_nullable, option_add_null_type, inline_subschemas) = (
+            settings.option_nullable,
+            settings.option_add_null_type,
+            settings.inline_subschemas,
+        );
+        let definitions_path = settings.definitions_path.clone();
+        let definitions_path = definitions_path.as_str();
+        let meta_schema = settings.meta_schema.as_deref();
+        let results: Vec<(Vec<Schema>, Map<String, Schema>)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = types
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        let mut gen = SchemaSettings::openapi3()
+                            .with(|s| {
+                                s.option_nullable = option_nullable;
+                                s.option_add_null_type = option_add_null_type;
+                                s.inline_subschemas = inline_subschemas;
+                                s.definitions_path = definitions_path.to_owned();
+                                s.meta_schema = meta_schema.map(str::to_owned);
+                                s.visitors = Vec::new();
+                            })
+                            .into_generator();
+                        let schemas: Vec<Schema> =
+                            chunk.iter().map(|item| (item.generate)(&mut gen)).collect();
+                        (schemas, gen.take_definitions())
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|err| std::panic::resume_unwind(err))
+                })
+                .collect()
+        });
+        // Each thread names the schemas on its own, check that a name is never used for two
+        // different types before using the definitions.
+        let mut schema_ids: Map<String, &str> = Map::new();
+        let mut collision = false;
+        for ((schemas, _), chunk) in results.iter().zip(types.chunks(chunk_size)) {
+            for (schema, item) in schemas.iter().zip(chunk) {
+                if let Some(name) = reference_name(schema, definitions_path) {
+                    let id = schema_ids.entry(name.to_owned()).or_insert(&item.schema_id);
+                    collision |= *id != item.schema_id;
+                }
+            }
+        }
+        let mut definitions: Map<String, Schema> = Map::new();
+        for (_, chunk_definitions) in &results {
+            for (name, schema) in chunk_definitions {
+                let existing = self
+                    .schema_generator
+                    .definitions()
+                    .get(name)
+                    .or_else(|| definitions.get(name));
+                match existing {
+                    Some(existing) => collision |= existing != schema,
+                    // This generator only learns the names of the types in `types`, a new
+                    // name for any other type could be given to a different type later.
+                    None if !schema_ids.contains_key(name) => collision = true,
+                    None => {
+                        definitions.insert(name.clone(), schema.clone());
+                    }
+                }
+            }
+        }
+        if collision {
+            let schemas = self.generate_schemas(&types);
+            return (self.name_schemas(before.as_ref(), schemas), false);
+        }
+        let added: HashSet<String> = definitions.keys().cloned().collect();
+        self.schema_generator.definitions_mut().extend(definitions);
+        // This lets the generator know the names of the types, and creates the references.
+        let schemas = self.generate_schemas(&types);
+        let same_names = results
+            .iter()
+            .flat_map(|(chunk_schemas, _)| chunk_schemas)
+            .zip(&schemas)
+            .all(|(thread_schema, (_, schema))| {
+                reference_name(thread_schema, definitions_path)
+                    == reference_name(schema, definitions_path)
+            });
+        if same_names {
+            return (self.name_schemas(before.as_ref(), schemas), true);
+        }
+        // The generator already used the name of a type for a different type, so the
+        // definitions from the other threads can not be used.
+        self.schema_generator
+            .definitions_mut()
+            .retain(|name, _| !added.contains(name));
+        let schemas = self.generate_schemas(&types);
+        (self.name_schemas(before.as_ref(), schemas), false)
+    }
+
+    /// Generate the schemas of the `types` on the current thread.
+    fn generate_schemas(&mut self, types: &[ParallelSchema]) -> Vec<(Cow<'static, str>, Schema)> {
+        types
+            .iter()
+            .map(|item| {
+                let schema = (item.generate)(&mut self.schema_generator);
+                (item.schema_id.clone(), schema)
+            })
+            .collect()
+    }
+
+    /// Obtain the internal `SchemaGenerator` object.
+    #[must_use]
+    pub fn schema_generator(&self) -> &SchemaGenerator {
+        &self.schema_generator
+    }
+
+    /// Obtain mutable access to the internal `SchemaGenerator` object, for example to add a
+    /// definition by hand. The names of added definitions need to match the `$ref`s that point
+    /// to them, these use the `definitions_path` of the
+    /// [`schema_settings`](OpenApiSettings::schema_settings).
+    pub fn schema_generator_mut(&mut self) -> &mut SchemaGenerator {
+        &mut self.schema_generator
+    }
+
+    /// Get the schemas that were generated so far, these are added to `components.schemas`
+    /// of the generated output. More schemas can still be added after this.
+    #[must_use]
+    pub fn definitions(&self) -> &Map<String, Schema> {
+        self.schema_generator.definitions()
+    }
+
+    /// Return the component definition/schema of an object without any references.
+    pub fn json_schema_no_ref<T: ?Sized + JsonSchema>(&mut self) -> SchemaObject {
+        let before = self.definition_names();
+        let schema = <T>::json_schema(&mut self.schema_generator);
+        self.name_schemas(before.as_ref(), vec![(T::schema_id(), schema)])
+            .remove(0)
+    }
+
+    /// Add a string schema with the `values` as `enum` to the definitions, for enums that are
+    /// only known at runtime, like country codes from a database. Returns a reference to the
+    /// schema, use `SchemaObject::new_ref` with its `reference` to use it as a schema.
+    ///
+    /// Adding the same values again with the same `name` does nothing. When a different schema
+    /// with the `name` exists it is kept and an [`OpenApiWarning::DuplicateSchemaName`] warning
+    /// is added.
+    pub fn add_string_enum_schema(
+        &mut self,
+        name: &str,
+        values: Vec<String>,
+    ) -> RefOr<SchemaObject> {
+        let schema = Schema::Object(SchemaObject {
+            instance_type: Some(InstanceType::String.into()),
+            enum_values: Some(values.into_iter().map(serde_json::Value::String).collect()),
+            ..SchemaObject::default()
+        });
+        match self.schema_generator.definitions().get(name) {
+            Some(existing) if *existing != schema => {
+                self.add_warning(OpenApiWarning::DuplicateSchemaName {
+                    name: name.to_owned(),
+                });
+            }
+            Some(_) => {}
+            None => {
+                self.schema_generator
+                    .definitions_mut()
+                    .insert(name.to_owned(), schema);
+            }
+        }
+        RefOr::Ref(Ref {
+            reference: format!("{}{}", self.settings.schema_settings.definitions_path, name),
+        })
+    }
+
+    /// Add a `discriminator` to the schema with the `schema_name` in the definitions.
+    /// The schema needs to be a `oneOf` (like a tagged enum) and all the variants need to
+    /// have the `property`. The `mapping` maps the values of the `property` to schema names or
+    /// references, this can be left empty if the values are the schema names.
+    ///
+    /// Returns an error if the schema does not exist, is not a `oneOf` or a variant does not
+    /// have the property.
+    pub fn set_discriminator(
+        &mut self,
+        schema_name: &str,
+        property: &str,
+        mapping: Map<String, String>,
+    ) -> Result<()> {
+        let prefix = &self.settings.schema_settings.definitions_path;
+        let definitions = self.schema_generator.definitions();
+        let variants = match definitions.get(schema_name) {
+            Some(Schema::Object(SchemaObject {
+                subschemas: Some(subschemas),
+                ..
+            })) => subschemas.one_of.as_ref(),
+            Some(_) => None,
+            None => {
+                return Err(OpenApiError::new(format!(
+                    "No schema named `{}` was found.",
+                    schema_name
+                )))
+            }
+        };
+        let variants = variants.ok_or_else(|| {
+            OpenApiError::new(format!("The schema `{}` is not a `oneOf`.", schema_name))
+        })?;
+        if !variants
+            .iter()
+            .all(|variant| schema_has_property(variant, property, definitions, prefix))
+        {
+            return Err(OpenApiError::new(format!(
+                "Not all variants of the schema `{}` have the property `{}`.",
+                schema_name, property
+            )));
+        }
+        let discriminator = Discriminator {
+            property_name: property.to_owned(),
+            mapping,
+            ..Discriminator::default()
+        };
+        let discriminator = serde_json::to_value(discriminator)
+            .expect("Could not serialize Discriminator as JSON.");
+        if let Some(Schema::Object(schema)) =
+            self.schema_generator.definitions_mut().get_mut(schema_name)
+        {
+            schema
+                .extensions
+                .insert("discriminator".to_owned(), discriminator);
+        }
+        Ok(())
+    }
+
+    /// Mark the `property` of the schema with the `schema_name` in the definitions as
+    /// `deprecated`, for example when the field will be removed in a future version.
+    /// A property that is a reference is wrapped in an `allOf`, because other keywords next to
+    /// `$ref` are ignored in OpenAPI 3.0.
+    ///
+    /// Returns an error if the schema or the property does not exist.
+    pub fn deprecate_property(&mut self, schema_name: &str, property: &str) -> Result<()> {
+        let property_schema = self.property_schema_mut(schema_name, property)?;
+        wrap_ref_property(property_schema).metadata().deprecated = true;
+        Ok(())
+    }
+
+    /// Set the `readOnly` and `writeOnly` flags of the `property` of the schema with the
+    /// `schema_name` in the definitions, for example for an `id` that is set by the server.
+    /// Like in [`deprecate_property`](Self::deprecate_property), a property that is a
+    /// reference is wrapped in an `allOf`.
+    ///
+    /// Returns an error if the schema or the property does not exist.
+    pub fn set_property_access(
+        &mut self,
+        schema_name: &str,
+        property: &str,
+        access: PropertyAccess,
+    ) -> Result<()> {
+        let property_schema = self.property_schema_mut(schema_name, property)?;
+        let (read_only, write_only) = match access {
+            PropertyAccess::ReadOnly => (true, false),
+            PropertyAccess::WriteOnly => (false, true),
+            PropertyAccess::ReadWrite => match property_schema {
+                Schema::Object(SchemaObject {
+                    metadata: Some(metadata),
+                    ..
+                }) => {
+                    metadata.read_only = false;
+                    metadata.write_only = false;
+                    return Ok(());
+                }
+                _ => return Ok(()),
+            },
+        };
+        let metadata = wrap_ref_property(property_schema).metadata();
+        metadata.read_only = read_only;
+        metadata.write_only = write_only;
+        Ok(())
+    }
+
+    /// Only allow the `value` for the `property` of the schema with the `schema_name` in the
+    /// definitions, for example for the `type` property of a variant in a `oneOf`. This uses
+    /// `const` for OpenAPI 3.1 and an `enum` with the single value for OpenAPI 3.0, which has
+    /// no `const`. A property that is a reference is wrapped in an `allOf`.
+    ///
+    /// Returns an error if the schema or the property does not exist.
+    pub fn set_property_const(
+        &mut self,
+        schema_name: &str,
+        property: &str,
+        value: serde_json::Value,
+    ) -> Result<()> {
+        let version = self.settings.version;
+        let property_schema = self.property_schema_mut(schema_name, property)?;
+        let schema = wrap_ref_property(property_schema);
+        if version == OpenApiVersion::V3_1 {
+            schema.const_value = Some(value);
+        } else {
+            schema.enum_values = Some(vec![value]);
+        }
+        Ok(())
+    }
+
+    /// Set the `minimum`, `maximum`, `minLength`, `maxLength` and `pattern` of the `property` of
+    /// the schema with the `schema_name` in the definitions, for constraints that are checked
+    /// by the application but are not part of the generated schema.
+    ///
+    /// Adds an [`OpenApiWarning::InvalidConstraint`] warning when a numeric constraint is set
+    /// on a property that is not a number or a string constraint on a property that is not a
+    /// string. Returns an error if the schema or the property does not exist.
+    pub fn set_property_constraints(
+        &mut self,
+        schema_name: &str,
+        property: &str,
+        constraints: Constraints,
+    ) -> Result<()> {
+        let property_schema = self.property_schema_mut(schema_name, property)?;
+        let schema = wrap_ref_property(property_schema);
+        let mut invalid = Vec::new();
+        if constraints.minimum.is_some() || constraints.maximum.is_some() {
+            if !has_instance_type(schema, &[InstanceType::Number, InstanceType::Integer]) {
+                invalid.push("minimum/maximum");
+            }
+            let number = schema.number();
+            number.minimum = constraints.minimum.or(number.minimum);
+            number.maximum = constraints.maximum.or(number.maximum);
+        }
+        if constraints.min_length.is_some()
+            || constraints.max_length.is_some()
+            || constraints.pattern.is_some()
+        {
+            if !has_instance_type(schema, &[InstanceType::String]) {
+                invalid.push("minLength/maxLength/pattern");
+            }
+            let string = schema.string();
+            string.min_length = constraints.min_length.or(string.min_length);
+            string.max_length = constraints.max_length.or(string.max_length);
+            if let Some(pattern) = constraints.pattern {
+                string.pattern = Some(pattern);
+            }
+        }
+        for constraint in invalid {
+            self.add_warning(OpenApiWarning::InvalidConstraint {
+                schema_name: schema_name.to_owned(),
+                property: property.to_owned(),
+                constraint: constraint.to_owned(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Get the schema of the `property` of the schema with the `schema_name` in the definitions.
+    fn property_schema_mut(&mut self, schema_name: &str, property: &str) -> Result<&mut Schema> {
+        let schema = match self.schema_generator.definitions_mut().get_mut(schema_name) {
+            Some(Schema::Object(schema)) => schema,
+            Some(Schema::Bool(_)) => {
+                return Err(OpenApiError::new(format!(
+                    "The schema `{}` is not an object.",
+                    schema_name
+                )))
+            }
+            None => {
+                return Err(OpenApiError::new(format!(
+                    "No schema named `{}` was found.",
+                    schema_name
+                )))
+            }
+        };
+        schema
+            .object
+            .as_mut()
+            .and_then(|object| object.properties.get_mut(property))
+            .ok_or_else(|| {
+                OpenApiError::new(format!(
+                    "The schema `{}` has no property `{}`.",
+                    schema_name, property
+                ))
+            })
+    }
+
+    /// Check that the property names of all the schemas in the definitions use the `casing`,
+    /// for example to find types that are missing a `#[serde(rename_all = "camelCase")]`.
+    /// This is best-effort: only the properties in the schemas are checked, not how serde
+    /// actually serializes the types.
+    ///
+    /// Returns a message for every property that does not use the `casing`.
+    #[must_use]
+    pub fn check_serde_casing(&self, casing: PropertyCasing) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (name, schema) in self.schema_generator.definitions() {
+            let mut properties = Vec::new();
+            collect_property_names(schema, &mut properties);
+            for property in properties {
+                if !casing.matches(property) {
+                    problems.push(format!(
+                        "The property `{}` of the schema `{}` does not use {:?}.",
+                        property, name, casing
+                    ));
+                }
+            }
+        }
+        problems
+    }
+
+    /// Add the `x-enum-varnames` extension to the schema with the `schema_name` in the
+    /// definitions, which client generators use as the names of the `enum` values.
+    /// The `names` are in the same order as the values.
+    ///
+    /// Returns an error if the schema does not exist, has no `enum` or the number of `names`
+    /// does not match the number of values.
+    pub fn set_enum_varnames(&mut self, schema_name: &str, names: Vec<String>) -> Result<()> {
+        let schema = match self.schema_generator.definitions_mut().get_mut(schema_name) {
+            Some(Schema::Object(schema)) => schema,
+            _ => {
+                return Err(OpenApiError::new(format!(
+                    "No schema named `{}` was found.",
+                    schema_name
+                )))
+            }
+        };
+        let count = match &schema.enum_values {
+            Some(values) => values.len(),
+            None => {
+                return Err(OpenApiError::new(format!(
+                    "The schema `{}` has no `enum`.",
+                    schema_name
+                )))
+            }
+        };
+        if count != names.len() {
+            return Err(OpenApiError::new(format!(
+                "The schema `{}` has {} `enum` values, but {} names are given.",
+                schema_name,
+                count,
+                names.len()
+            )));
+        }
+        schema
+            .extensions
+            .insert("x-enum-varnames".to_owned(), serde_json::json!(names));
+        Ok(())
+    }
+
+    /// Set the `additionalProperties` of the object schema with the `schema_name` in the
+    /// definitions, for example to limit the values of a map type to a specific schema.
+    ///
+    /// Returns an error if the schema does not exist.
+    pub fn set_additional_properties(
+        &mut self,
+        schema_name: &str,
+        value: AdditionalProperties,
+    ) -> Result<()> {
+        let value = match value {
+            AdditionalProperties::Bool(allowed) => Schema::Bool(allowed),
+            AdditionalProperties::Schema(schema) => match *schema {
+                RefOr::Ref(reference) => Schema::new_ref(reference.reference),
+                RefOr::Object(schema) => Schema::Object(schema),
+            },
+        };
+        match self.schema_generator.definitions_mut().get_mut(schema_name) {
+            Some(Schema::Object(schema)) => {
+                schema.object().additional_properties = Some(Box::new(value));
+                Ok(())
+            }
+            Some(Schema::Bool(_)) => Err(OpenApiError::new(format!(
+                "The schema `{}` is not an object.",
+                schema_name
+            ))),
+            None => Err(OpenApiError::new(format!(
+                "No schema named `{}` was found.",
+                schema_name
+            ))),
+        }
+    }
+
+    /// Rewrite the schema with the `schema_name` in the definitions to an `allOf` of references to
+    /// the `bases` and an object with the remaining properties that are not part of any of the
+    /// bases. This is useful for types that `#[serde(flatten)]` a shared struct.
+    ///
+    /// Returns an error if the schema or one of the bases does not exist or is not an object.
+    pub fn compose_all_of(&mut self, schema_name: &str, bases: &[&str]) -> Result<()> {
+        let definitions = self.schema_generator.definitions();
+        let object_schema = |name: &str| match definitions.get(name) {
+            Some(Schema::Object(
+                schema @ SchemaObject {
+                    object: Some(_), ..
+                },
+            )) => Ok(schema),
+            Some(_) => Err(OpenApiError::new(format!(
+                "The schema `{}` is not an object.",
+                name
+            ))),
+            None => Err(OpenApiError::new(format!(
+                "No schema named `{}` was found.",
+                name
+            ))),
+        };
+        let mut own = object_schema(schema_name)?.clone();
+        let mut all_of = Vec::new();
+        for base in bases {
+            let base_object = object_schema(base)?.object.as_ref().expect("checked above");
+            let own_object = own.object();
+            for property in base_object.properties.keys() {
+                own_object.properties.remove(property);
+                own_object.required.remove(property);
+            }
+            all_of.push(Schema::new_ref(format!(
+                "{}{}",
+                self.settings.schema_settings.definitions_path, base
+            )));
+        }
+        let mut composed = SchemaObject {
+            metadata: own.metadata.take(),
+            extensions: std::mem::take(&mut own.extensions),
+            ..SchemaObject::default()
+        };
+        if !own.object().properties.is_empty() {
+            all_of.push(own.into());
+        }
+        composed.subschemas().all_of = Some(all_of);
+        self.schema_generator
+            .definitions_mut()
+            .insert(schema_name.to_owned(), composed.into());
+        Ok(())
+    }
+
+    /// Iterate over all the operations that were added, with their path and method.
+    /// The order of the operations is not specified.
+    pub fn operations(&self) -> impl Iterator<Item = (&str, Method, &Operation)> {
+        self.operations.iter().flat_map(|(path, map)| {
+            map.iter()
+                .map(move |(method, op)| (path.as_str(), *method, op))
+        })
+    }
+
+    /// Remove the operations for which `pred` returns `false`, for example to hide internal
+    /// endpoints from a public document. Schemas that were only used by the removed operations
+    /// are removed as well, schemas that are still used are kept.
+    pub fn retain_operations<F: Fn(&str, Method, &Operation) -> bool>(&mut self, pred: F) {
+        let prefix = self.settings.schema_settings.definitions_path.clone();
+        let mut removed_refs = Vec::new();
+        for (path, map) in &mut self.operations {
+            map.retain(|method, op| {
+                let keep = pred(path, *method, op);
+                if !keep {
+                    collect_schema_references(&to_json(op), &prefix, &mut removed_refs);
+                }
+                keep
+            });
+        }
+        self.operations.retain(|_, map| !map.is_empty());
+        let candidates = self.referenced_schemas(removed_refs);
+        let used = self.referenced_schemas(self.schema_roots());
+        self.schema_generator
+            .definitions_mut()
+            .retain(|name, _| !candidates.contains(name) || used.contains(name));
+    }
+
+    /// Remove the schemas that are not used by any of the operations, component responses and
+    /// parameters, catcher responses, path parameters or webhooks, directly or through other
+    /// schemas. Call this after all
+    /// operations are added. Recursive types are kept when they are used.
+    pub fn prune_unused_schemas(&mut self) {
+        let used = self.referenced_schemas(self.schema_roots());
+        self.schema_generator
+            .definitions_mut()
+            .retain(|name, _| used.contains(name));
+    }
+
+    /// Get the names of the schemas that are referenced directly by the operations, component
+    /// responses and parameters, catcher responses, path parameters and webhooks.
+    fn schema_roots(&self) -> Vec<String> {
+        let prefix = &self.settings.schema_settings.definitions_path;
+        let mut names = Vec::new();
+        for op in self.operations.values().flat_map(HashMap::values) {
+            collect_schema_references(&to_json(op), prefix, &mut names);
+        }
+        collect_schema_references(&to_json(&self.responses), prefix, &mut names);
+        collect_schema_references(&to_json(&self.catcher_responses), prefix, &mut names);
+        collect_schema_references(&to_json(&self.parameters), prefix, &mut names);
+        collect_schema_references(&to_json(&self.path_parameters), prefix, &mut names);
+        collect_schema_references(&to_json(&self.webhooks), prefix, &mut names);
+        names
+    }
+
+    /// Get the names of the given schemas and all the schemas they reference (recursively).
+    fn referenced_schemas(&self, names: Vec<String>) -> HashSet<String> {
+        let prefix = &self.settings.schema_settings.definitions_path;
+        let definitions = self.schema_generator.definitions();
+        let mut found = HashSet::new();
+        let mut pending = names;
+        while let Some(name) = pending.pop() {
+            if !found.insert(name.clone()) {
+                continue;
+            }
+            if let Some(schema) = definitions.get(&name) {
+                collect_schema_references(&to_json(schema), prefix, &mut pending);
+            }
+        }
+        found
+    }
+
+    /// Remove schemas that are structurally identical to a schema that was added before them,
+    /// and update all references to point to the remaining schema, in the definitions,
+    /// operations, component responses and parameters, catcher responses, path parameters and
+    /// webhooks.
+    /// The `title` of the schemas is ignored when comparing them.
+    pub fn dedup_schemas(&mut self) {
+        let prefix = self.settings.schema_settings.definitions_path.clone();
+        loop {
+            let definitions = self.schema_generator.definitions();
+            let schemas: Vec<(&String, Schema)> = definitions
+                .iter()
+                .map(|(name, schema)| (name, without_title(schema)))
+                .collect();
+            let mut renames: Map<String, String> = Map::new();
+            for (index, (name, schema)) in schemas.iter().enumerate() {
+                if renames.contains_key(*name) {
+                    continue;
+                }
+                for (other, other_schema) in &schemas[index + 1..] {
+                    if !renames.contains_key(*other) && schema == other_schema {
+                        renames.insert((*other).clone(), (*name).clone());
+                    }
+                }
+            }
+            if renames.is_empty() {
+                return;
+            }
+            let mut visitor = ReplaceReferences {
+                references: renames
+                    .iter()
+                    .map(|(old, new)| (format!("{}{}", prefix, old), format!("{}{}", prefix, new)))
+                    .collect(),
+            };
+            let definitions = self.schema_generator.definitions_mut();
+            definitions.retain(|name, _| !renames.contains_key(name));
+            for schema in definitions.values_mut() {
+                visitor.visit_schema(schema);
+            }
+            for operation in self
+                .operations
+                .values_mut()
+                .flat_map(|map| map.values_mut())
+            {
+                visit_operation(&mut visitor, operation);
+            }
+            for response in self
+                .responses
+                .values_mut()
+                .chain(self.catcher_responses.values_mut())
+            {
+                visit_response(&mut visitor, response);
+            }
+            for parameter in self.parameters.values_mut() {
+                visit_parameter(&mut visitor, parameter);
+            }
+            for parameter in self.path_parameters.values_mut().flatten() {
+                if let RefOr::Object(parameter) = parameter {
+                    visit_parameter(&mut visitor, parameter);
+                }
+            }
+            for path_item in self.webhooks.values_mut() {
+                visit_path_item(&mut visitor, path_item);
+            }
+        }
+    }
+
+    /// Generate an `OpenApi` specification for all added operations.
+    ///
+    /// The output is the same every time for the same input: the paths are in the order they
+    /// were first added and operations are handled in a fixed order of methods.
+    /// The schemas are in the order they were first used. (When the `preserve_order` feature is
+    /// disabled paths and schemas are sorted by name instead.)
+    ///
+    /// The warnings are logged, use
+    /// [`into_openapi_with_warnings`](Self::into_openapi_with_warnings) to get them.
+    #[must_use]
+    pub fn into_openapi(self) -> OpenApi {
+        self.into_openapi_with_warnings().0
+    }
+
+    /// Generate an `OpenApi` specification for all added operations like
+    /// [`into_openapi`](Self::into_openapi), and write it as JSON to the file at the `path`, for
+    /// example from a `build.rs` or a binary to commit the specification. Missing parent
+    /// directories are created. When `pretty` is `true` the JSON is indented.
+    pub fn write_to_file(self, path: &Path, pretty: bool) -> std::io::Result<()> {
+        let spec = self.into_openapi();
+        let json = if pretty {
+            serde_json::to_vec_pretty(&spec)?
+        } else {
+            serde_json::to_vec(&spec)?
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, json)
+    }
+
+    /// Create a standalone JSON Schema (2020-12) document with all the generated schemas in
+    /// `$defs`, for tools that use plain JSON Schema instead of OpenAPI. The schemas are named
+    /// like in `components.schemas` and their `$ref`s point to `#/$defs/`. The OpenAPI 3.0
+    /// `nullable` keyword is replaced with a `null` type, and `examples` are kept instead of
+    /// being replaced with `example`.
+    #[must_use]
+    pub fn into_json_schema_bundle(mut self) -> serde_json::Value {
+        let prefix = self.settings.schema_settings.definitions_path.clone();
+        let mut schemas = self.schema_generator.take_definitions();
+        let mut references = ReplaceReferences {
+            references: schemas
+                .keys()
+                .map(|name| (format!("{}{}", prefix, name), format!("#/$defs/{}", name)))
+                .collect(),
+        };
+        for schema in schemas.values_mut() {
+            // `SetSingleExample` replaces `examples` with the OpenAPI 3.0 `example` keyword,
+            // which is not part of JSON Schema.
+            for visitor in self
+                .schema_generator
+                .visitors_mut()
+                .filter(|visitor| !visitor.as_any().is::<SetSingleExample>())
+            {
+                visitor.visit_schema(schema);
+            }
+            ReplaceNullableWithNullType.visit_schema(schema);
+            references.visit_schema(schema);
+        }
+        serde_json::json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "$defs": schemas,
+        })
+    }
+
+    /// Generate an `OpenApi` specification for all added operations, together with all the
+    /// warnings collected while adding the operations and generating the specification.
+    #[must_use]
+    pub fn into_openapi_with_warnings(mut self) -> (OpenApi, Vec<OpenApiWarning>) {
+        if self.settings.info.title.is_empty() || self.settings.info.version.is_empty() {
+            self.add_warning(OpenApiWarning::EmptyInfo);
+        }
+        for error in self.validate_path_params() {
+            self.add_warning(OpenApiWarning::InvalidOperation(error));
+        }
+        let unknown_schemes: Vec<String> = self
+            .global_security
+            .iter()
+            .flat_map(|requirement| requirement.keys())
+            .filter(|name| !self.security_schemes.contains_key(*name))
+            .cloned()
+            .collect();
+        for name in unknown_schemes {
+            self.add_warning(OpenApiWarning::UnknownSecurityScheme { name });
+        }
+        let mut warnings = self.take_warnings();
+        let collision = self.settings.operation_id_collision;
+        let (mut spec, dropped) = self.build_openapi();
+        for error in dropped {
+            let warning = OpenApiWarning::OperationError(error);
+            log::warn!("{}", warning);
+            warnings.push(warning);
+        }
+        for operation_id in ensure_unique_operation_ids(&mut spec, collision) {
+            let warning = OpenApiWarning::InvalidOperation(OpenApiError::DuplicateOperationId {
+                operation_id,
+            });
+            log::warn!("{}", warning);
+            warnings.push(warning);
+        }
+        for (name, operation_id) in unknown_link_operations(&spec) {
+            let warning = OpenApiWarning::InvalidOperation(OpenApiError::UnknownLinkOperation {
+                name,
+                operation_id,
+            });
+            log::warn!("{}", warning);
+            warnings.push(warning);
+        }
+        if let Err(references) = spec.validate_refs() {
+            for reference in references {
+                let warning = OpenApiWarning::UnresolvedRef { reference };
+                log::warn!("{}", warning);
+                warnings.push(warning);
+            }
+        }
+        (spec, warnings)
+    }
+
+    /// Check that the `{name}` placeholders in the paths match the `in: path` parameters of
+    /// the operations, including the parameters of their path added with
+    /// [`set_path_parameters`](Self::set_path_parameters). Parameters that reference
+    /// `components.parameters` are resolved.
+    ///
+    /// Returns an [`OpenApiError::MissingPathParameter`] for every placeholder without a
+    /// parameter and an [`OpenApiError::UnknownPathParameter`] for every path parameter without
+    /// a placeholder. These are also added as warnings by
+    /// [`into_openapi_with_warnings`](Self::into_openapi_with_warnings).
+    #[must_use]
+    pub fn validate_path_params(&self) -> Vec<OpenApiError> {
+        let mut errors = Vec::new();
+        for (path, map) in &self.operations {
+            let placeholders = path_placeholders(path);
+            let mut methods: Vec<_> = map.iter().collect();
+            methods.sort_by_key(|(method, _)| method_order(**method));
+            for (method, op) in methods {
+                let names: Vec<&str> = op
+                    .parameters
+                    .iter()
+                    .chain(self.path_parameters.get(path).into_iter().flatten())
+                    .filter_map(|param| match param {
+                        RefOr::Object(param) => Some(param),
+                        RefOr::Ref(reference) => reference
+                            .reference
+                            .strip_prefix("#/components/parameters/")
+                            .and_then(|name| self.parameters.get(name)),
+                    })
+                    .filter(|param| param.location == "path")
+                    .map(|param| param.name.as_str())
+                    .collect();
+                for placeholder in &placeholders {
+                    if !names.contains(placeholder) {
+                        errors.push(OpenApiError::MissingPathParameter {
+                            path: path.clone(),
+                            method: *method,
+                            name: (*placeholder).to_owned(),
+                        });
+                    }
+                }
+                for name in names {
+                    if !placeholders.contains(&name) {
+                        errors.push(OpenApiError::UnknownPathParameter {
+                            path: path.clone(),
+                            method: *method,
+                            name: name.to_owned(),
+                        });
+                    }
+                }
+            }
+        }
+        errors
+    }
+
+    /// Get the path and method of all the operations that have no `summary` or an empty
+    /// `summary`, sorted by path.
+    #[must_use]
+    pub fn check_documentation(&self) -> Vec<(String, Method)> {
+        let mut undocumented = Vec::new();
+        for (path, map) in &self.operations {
+            for (method, op) in map {
+                let documented = matches!(&op.summary, Some(summary) if !summary.trim().is_empty());
+                if !documented {
+                    undocumented.push((path.clone(), *method));
+                }
+            }
+        }
+        undocumented.sort_by(|(path1, method1), (path2, method2)| {
+            path1
+                .cmp(path2)
+                .then_with(|| method_order(*method1).cmp(&method_order(*method2)))
+        });
+        undocumented
+    }
+
+    /// Generate an `OpenApi` specification for all added operations, and check that it is
+    /// complete enough to be published. Unlike [`into_openapi`](Self::into_openapi) this
+    /// returns all the problems that are found:
+    /// - [`OpenApiError::EmptyInfoField`] when `info.title` or `info.version` is empty.
+    /// - [`OpenApiError::MissingResponses`] for operations that still have no responses
+    ///   after [`OpenApiSettings::default_response`] and the catcher responses are added.
+    /// - [`OpenApiError::UnresolvedRef`] for every `$ref` that does not point to anything.
+    /// - [`OpenApiError::DuplicateOperationId`] when `operationId`s are not unique and
+    ///   [`OpenApiSettings::operation_id_collision`] is `OperationIdCollision::Error`.
+    /// - [`OpenApiError::MissingPathParameter`] and [`OpenApiError::UnknownPathParameter`] when
+    ///   the path parameters do not match the path, see
+    ///   [`validate_path_params`](Self::validate_path_params).
+    /// - [`OpenApiError::MissingSummary`] for operations without a summary, see
+    ///   [`check_documentation`](Self::check_documentation).
+    /// - The errors of operations that could not be added, like
+    ///   [`OpenApiError::DuplicateOperation`] for [`CollisionPolicy::Error`] and
+    ///   [`OpenApiError::UnsupportedMethod`] for [`ConnectHandling::Error`].
+    /// - The errors of servers with invalid variables, see [`add_server`](Self::add_server).
+    /// - [`OpenApiError::InvalidExample`] for examples that do not match their schema.
+    pub fn into_openapi_strict(self) -> std::result::Result<OpenApi, Vec<OpenApiError>> {
+        let missing_summaries = self.check_documentation();
+        let (spec, warnings) = self.into_openapi_with_warnings();
+        // Check the generated document, so the responses added by
+        // `OpenApiSettings::default_response` and `add_catcher_response` are included.
+        let mut errors = Vec::new();
+        if spec.info.title.is_empty() {
+            errors.push(OpenApiError::EmptyInfoField { field: "title" });
+        }
+        if spec.info.version.is_empty() {
+            errors.push(OpenApiError::EmptyInfoField { field: "version" });
+        }
+        for (path, path_item) in &spec.paths {
+            let operations = [
+                (Method::Get, &path_item.get),
+                (Method::Put, &path_item.put),
+                (Method::Post, &path_item.post),
+                (Method::Delete, &path_item.delete),
+                (Method::Options, &path_item.options),
+                (Method::Head, &path_item.head),
+                (Method::Patch, &path_item.patch),
+                (Method::Trace, &path_item.trace),
+            ];
+            for (method, op) in operations.iter() {
+                if let Some(op) = op {
+                    if op.responses.default.is_none() && op.responses.responses.is_empty() {
+                        errors.push(OpenApiError::MissingResponses {
+                            path: path.clone(),
+                            method: *method,
+                        });
+                    }
+                }
+            }
+        }
+        for (path, method) in missing_summaries {
+            errors.push(OpenApiError::MissingSummary { path, method });
+        }
+        for warning in warnings {
+            match warning {
+                OpenApiWarning::UnresolvedRef { reference } => {
+                    errors.push(OpenApiError::UnresolvedRef { reference });
+                }
+                OpenApiWarning::InvalidOperation(error)
+                | OpenApiWarning::OperationError(error)
+                | OpenApiWarning::InvalidServer(error) => errors.push(error),
+                OpenApiWarning::InvalidExample {
+                    path,
+                    method,
+                    content_type,
+                } => errors.push(OpenApiError::InvalidExample {
+                    path,
+                    method,
+                    content_type,
+                }),
+                _ => {}
+            }
+        }
+        if errors.is_empty() {
+            Ok(spec)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Build the document. Returns the errors of operations that are left out because another
+    /// operation has the same path and method once the `base_path` is added.
+    fn build_openapi(self) -> (OpenApi, Vec<OpenApiError>) {
+        let mut schema_generator = self.schema_generator;
+        let mut schemas = schema_generator.take_definitions();
+        if self.settings.schema_titles_from_name {
+            for (name, schema) in schemas.iter_mut() {
+                if let Schema::Object(schema) = schema {
+                    let metadata = schema.metadata();
+                    if metadata.title.is_none() {
+                        metadata.title = Some(name.clone());
+                    }
+                }
+            }
+        }
+
+        // Add the security schemes
+        let mut schemes: Map<String, RefOr<SecurityScheme>> = Default::default();
+        for (name, schema) in self.security_schemes {
+            schemes.insert(name, schema.into());
+        }
+
+        for visitor in schema_generator.visitors_mut() {
+            for schema in schemas.values_mut() {
+                visitor.visit_schema(schema)
+            }
+        }
+
+        let info = self.settings.info;
+        let default_security = self.default_security;
+        let inject_catcher_responses = self.settings.inject_catcher_responses;
+        let base_path = self.settings.base_path;
+        let trailing_slash = self.settings.trailing_slash;
+        let document_path = |path: &str| match &base_path {
+            Some(base_path) => prefix_path(base_path, path, trailing_slash),
+            None => path.to_owned(),
+        };
+        let mut dropped = Vec::new();
+        let mut spec = OpenApi {
+            openapi: self.settings.version.as_str().to_owned(),
+            info,
+            paths: {
+                let mut paths = Map::new();
+                for (path, map) in self.operations {
+                    let mut operations = map.into_iter().collect::<Vec<_>>();
+                    operations.sort_by_key(|(method, _)| method_order(*method));
+                    for (method, mut op) in operations {
+                        if op.security.is_none() && !default_security.is_empty() {
+                            op.security = Some(default_security.clone());
+                        }
+                        if let Some((status, response)) = &self.settings.default_response {
+                            if op.responses.responses.is_empty() && op.responses.default.is_none() {
+                                op.responses
+                                    .responses
+                                    .insert(status.clone(), response.clone().into());
+                            }
+                        }
+                        if inject_catcher_responses {
+                            for (status, response) in &self.catcher_responses {
+                                add_catcher_response(&mut op.responses, status, response);
+                            }
+                        }
+                        let path = document_path(&path);
+                        let path_item: &mut PathItem = paths.entry(path.clone()).or_default();
+                        if has_operation(path_item, method) {
+                            dropped.push(OpenApiError::DuplicateOperation { path, method });
+                        } else {
+                            set_operation(path_item, method, op);
+                        }
+                    }
+                }
+                for (path, servers) in self.path_servers {
+                    if let Some(path_item) = paths.get_mut(&document_path(&path)) {
+                        path_item.servers = Some(servers);
+                    }
+                }
+                for (path, params) in self.path_parameters {
+                    if let Some(path_item) = paths.get_mut(&document_path(&path)) {
+                        path_item.parameters = params;
+                    }
+                }
+                paths
+            },
+            components: Some(Components {
+                schemas: schemas.into_iter().map(|(k, v)| (k, v.into())).collect(),
+                responses: self
+                    .responses
+                    .into_iter()
+                    .chain(if inject_catcher_responses {
+                        Map::new()
+                    } else {
+                        self.catcher_responses
+                    })
+                    .map(|(name, response)| (name, response.into()))
+                    .collect(),
+                parameters: self
+                    .parameters
+                    .into_iter()
+                    .map(|(name, param)| (name, param.into()))
+                    .collect(),
+                security_schemes: schemes,
+                ..Default::default()
+            }),
+            security: self.global_security,
+            servers: self.servers,
+            tags: self.tags,
+            external_docs: self.external_docs,
+            extensions: self.extensions,
+            ..OpenApi::default()
+        };
+        let webhooks = self.webhooks;
+        if self.settings.version == OpenApiVersion::V3_1 {
+            spec.webhooks = webhooks;
+            visit_openapi(&mut ReplaceNullableWithNullType, &mut spec);
+            visit_openapi(&mut UnwrapRefWithSiblings, &mut spec);
+        } else {
+            if !webhooks.is_empty() {
+                let webhooks =
+                    serde_json::to_value(webhooks).expect("Could not serialize webhooks as JSON.");
+                spec.extensions.insert("x-webhooks".to_owned(), webhooks);
+            }
+            visit_openapi(&mut ReplaceNullTypeWithNullable, &mut spec);
+            visit_openapi(&mut WrapRefWithSiblings, &mut spec);
+        }
+        for visitor in &self.settings.schema_visitors {
+            visit_openapi(&mut ApplySchemaVisitor(visitor.as_ref()), &mut spec);
+        }
+        (spec, dropped)
+    }
+}
+
+fn add_catcher_response(responses: &mut Responses, status: &str, response: &Response) {
+    if status == "default" {
+        if responses.default.is_none() {
+            responses.default = Some(response.clone().into());
+        }
+    } else if !responses.responses.contains_key(status) {
+        responses
+            .responses
+            .insert(status.to_owned(), response.clone().into());
+    }
+}
+
+fn find_operation_mut<'a>(
+    operations: &'a mut Map<String, HashMap<Method, Operation>>,
+    path: &str,
+    method: Method,
+    trailing_slash: TrailingSlash,
+) -> Result<&'a mut Operation> {
+    operations
+        .get_mut(&normalize_path(path, trailing_slash))
+        .and_then(|map| map.get_mut(&method))
+        .ok_or_else(|| OpenApiError::MissingOperation {
+            path: path.to_owned(),
+            method,
+        })
+}
+
+fn to_example<T: Serialize>(value: &T) -> Result<serde_json::Value> {
+    Ok(serde_json::to_value(value)?)
+}
+
+/// Set the example of all the media types, returns the content types for which the example does
+/// not match the schema.
+fn set_example(
+    content: &mut Map<String, MediaType>,
+    example: serde_json::Value,
+    schema_generator: &SchemaGenerator,
+    settings: &OpenApiSettings,
+) -> Vec<String> {
+    let mut invalid = Vec::new();
+    for (content_type, media_type) in content {
+        if let Some(schema) = &media_type.schema {
+            if cfg!(debug_assertions)
+                && !value_matches_schema(
+                    &example,
+                    schema,
+                    schema_generator.definitions(),
+                    &settings.schema_settings.definitions_path,
+                )
+            {
+                invalid.push(content_type.clone());
+            }
+        }
+        media_type.example = Some(example.clone());
+    }
+    invalid
+}
+
+/// Make sure no two operations have the same `operationId`, the operations are checked in the
+/// order of the document. Returns the `operationId`s that are used multiple times when
+/// `collision` is `OperationIdCollision::Error`, otherwise those are renamed.
+fn ensure_unique_operation_ids(spec: &mut OpenApi, collision: OperationIdCollision) -> Vec<String> {
+    let mut all_ids = HashSet::new();
+    for path_item in spec.paths.values() {
+        for op in path_operations(path_item) {
+            if let Some(operation_id) = &op.operation_id {
+                all_ids.insert(operation_id.clone());
+            }
+        }
+    }
+    let mut seen = HashSet::new();
+    let mut duplicates = Vec::new();
+    for path_item in spec.paths.values_mut() {
+        let operations = [
+            &mut path_item.get,
+            &mut path_item.put,
+            &mut path_item.post,
+            &mut path_item.delete,
+            &mut path_item.options,
+            &mut path_item.head,
+            &mut path_item.patch,
+            &mut path_item.trace,
+        ];
+        for op in operations {
+            let operation_id = match op.as_mut().and_then(|op| op.operation_id.as_mut()) {
+                Some(operation_id) => operation_id,
+                None => continue,
+            };
+            if seen.insert(operation_id.clone()) {
+                continue;
+            }
+            match collision {
+                OperationIdCollision::Suffix => {
+                    let new_id = (2..)
+                        .map(|i| format!("{}_{}", operation_id, i))
+                        .find(|id| !all_ids.contains(id) && !seen.contains(id))
+                        .expect("There is always an unused suffix.");
+                    seen.insert(new_id.clone());
+                    *operation_id = new_id;
+                }
+                OperationIdCollision::Error => {
+                    if !duplicates.contains(operation_id) {
+                        duplicates.push(operation_id.clone());
+                    }
+                }
+            }
+        }
+    }
+    duplicates
+}
+
+/// The name of the definition that the `schema` refers to.
+fn reference_name<'a>(schema: &'a Schema, definitions_path: &str) -> Option<&'a str> {
+    match schema {
+        Schema::Object(SchemaObject {
+            reference: Some(reference),
+            ..
+        }) => reference.strip_prefix(definitions_path),
+        _ => None,
+    }
+}
+
+/// Replace repeated slashes in the `path` with a single slash, and remove the trailing slash
+/// when `trailing_slash` is `TrailingSlash::Strip`.
+fn normalize_path(path: &str, trailing_slash: TrailingSlash) -> String {
+    let mut normalized = String::with_capacity(path.len());
+    for c in path.chars() {
+        if !(c == '/' && normalized.ends_with('/')) {
+            normalized.push(c);
+        }
+    }
+    if trailing_slash == TrailingSlash::Strip && normalized.len() > 1 && normalized.ends_with('/') {
+        normalized.pop();
+    }
+    normalized
+}
+
+/// Add the `base_path` in front of the `path`, so `/api/v2/` and `/users` become
+/// `/api/v2/users`. The result is normalized with [`normalize_path`].
+fn prefix_path(base_path: &str, path: &str, trailing_slash: TrailingSlash) -> String {
+    let base_path = base_path.trim_matches('/');
+    let path = path.trim_start_matches('/');
+    let prefixed = match (base_path.is_empty(), path.is_empty()) {
+        (true, _) => format!("/{}", path),
+        (false, true) => format!("/{}", base_path),
+        (false, false) => format!("/{}/{}", base_path, path),
+    };
+    normalize_path(&prefixed, trailing_slash)
+}
+
+/// Add the names of the properties of the schema and its inline subschemas to `names`.
+/// References are not followed.
+fn collect_property_names<'a>(schema: &'a Schema, names: &mut Vec<&'a str>) {
+    let schema = match schema {
+        Schema::Object(schema) => schema,
+        Schema::Bool(_) => return,
+    };
+    if let Some(object) = &schema.object {
+        for (name, property) in &object.properties {
+            names.push(name);
+            collect_property_names(property, names);
+        }
+    }
+    if let Some(subschemas) = &schema.subschemas {
+        let lists = [&subschemas.all_of, &subschemas.any_of, &subschemas.one_of];
+        for list in lists.iter().copied().flatten() {
+            for subschema in list {
+                collect_property_names(subschema, names);
+            }
+        }
+    }
+}
+
+/// Returns `true` if the `type` of the schema is one of the `types`, or if the schema has no
+/// `type` (like an `allOf` around a reference), because then the type is not known.
+fn has_instance_type(schema: &SchemaObject, types: &[InstanceType]) -> bool {
+    match &schema.instance_type {
+        Some(instance_type) => match instance_type {
+            SingleOrVec::Single(single) => types.contains(single),
+            SingleOrVec::Vec(list) => list.iter().any(|item| types.contains(item)),
+        },
+        None => true,
+    }
+}
+
+/// Make the property schema an object that can have other keywords, a reference is wrapped in
+/// an `allOf` because keywords next to `$ref` are ignored in OpenAPI 3.0.
+fn wrap_ref_property(property_schema: &mut Schema) -> &mut SchemaObject {
+    let wrapped = match std::mem::replace(property_schema, Schema::Bool(true)) {
+        Schema::Object(schema) if schema.is_ref() => {
+            let mut wrapper = SchemaObject::default();
+            wrapper.subschemas().all_of = Some(vec![schema.into()]);
+            wrapper
+        }
+        Schema::Object(schema) => schema,
+        Schema::Bool(_) => SchemaObject::default(),
+    };
+    *property_schema = wrapped.into();
+    match property_schema {
+        Schema::Object(schema) => schema,
+        Schema::Bool(_) => unreachable!("The property schema was just set to an object."),
+    }
+}
+
+/// The schema of raw bytes: a `string` with the `binary` format.
+fn binary_schema() -> SchemaObject {
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        format: Some("binary".to_owned()),
+        ..SchemaObject::default()
+    }
+}
+
+/// Check that the `enum` of every variable of the server is not empty and contains the `default`.
+fn validate_server(server: &Server) -> Result<()> {
+    for (name, variable) in &server.variables {
+        match &variable.enumeration {
+            Some(values) if values.is_empty() => {
+                return Err(OpenApiError::new(format!(
+                    "The variable `{}` of the server `{}` has an empty `enum`.",
+                    name, server.url
+                )));
+            }
+            Some(values) if !values.contains(&variable.default) => {
+                return Err(OpenApiError::new(format!(
+                    "The default `{}` of the variable `{}` of the server `{}` is not in its `enum`.",
+                    variable.default, name, server.url
+                )));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Get the names of the `{name}` placeholders in the path.
+fn path_placeholders(path: &str) -> Vec<&str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+fn to_json<T: Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).expect("Could not serialize the value as JSON.")
+}
+
+/// Collect the names of the schemas referenced by the value, these are the `$ref`s that start
+/// with the `prefix`.
+fn collect_schema_references(value: &serde_json::Value, prefix: &str, names: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                match value {
+                    serde_json::Value::String(reference) if key == "$ref" => {
+                        if let Some(name) = reference.strip_prefix(prefix) {
+                            names.push(name.to_owned());
+                        }
+                    }
+                    _ => collect_schema_references(value, prefix, names),
+                }
+            }
+        }
+        serde_json::Value::Array(list) => {
+            for value in list {
+                collect_schema_references(value, prefix, names);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Get the name and `operationId` of all the links that refer to an `operationId` that is not
+/// in the document.
+fn unknown_link_operations(spec: &OpenApi) -> Vec<(String, String)> {
+    let operations: Vec<&Operation> = spec.paths.values().flat_map(path_operations).collect();
+    let operation_ids: Vec<&str> = operations
+        .iter()
+        .filter_map(|op| op.operation_id.as_deref())
+        .collect();
+    let component_responses = spec
+        .components
+        .iter()
+        .flat_map(|components| components.responses.values());
+    let responses = operations
+        .iter()
+        .flat_map(|op| {
+            op.responses
+                .default
+                .iter()
+                .chain(op.responses.responses.values())
+        })
+        .chain(component_responses);
+    let mut unknown = Vec::new();
+    for response in responses {
+        if let RefOr::Object(response) = response {
+            for (name, link) in &response.links {
+                if let RefOr::Object(Link {
+                    operation_id: Some(operation_id),
+                    ..
+                }) = link
+                {
+                    if !operation_ids.contains(&operation_id.as_str()) {
+                        unknown.push((name.clone(), operation_id.clone()));
+                    }
+                }
+            }
+        }
+    }
+    unknown
+}
+
+/// Get all the operations of the `PathItem`, in the order of `method_order`.
+fn path_operations(path_item: &PathItem) -> Vec<&Operation> {
+    let operations = [
+        path_item.get.as_ref(),
+        path_item.put.as_ref(),
+        path_item.post.as_ref(),
+        path_item.delete.as_ref(),
+        path_item.options.as_ref(),
+        path_item.head.as_ref(),
+        path_item.patch.as_ref(),
+        path_item.trace.as_ref(),
+    ];
+    operations.iter().flatten().copied().collect()
+}
+
+fn check_extension_key(key: &str) -> Result<()> {
+    if key.starts_with("x-") {
+        Ok(())
+    } else {
+        Err(OpenApiError::InvalidExtensionKey {
+            key: key.to_owned(),
+        })
+    }
+}
+
+fn external_docs(url: &str, description: Option<String>) -> Result<ExternalDocs> {
+    if url.trim().is_empty() {
+        return Err(OpenApiError::new(
+            "The url of the external documentation can not be empty.".to_owned(),
+        ));
+    }
+    Ok(ExternalDocs {
+        url: url.to_owned(),
+        description,
+        ..ExternalDocs::default()
+    })
+}
+
+/// Check if the schema (or one of the schemas in its `allOf`) has the property.
+fn schema_has_property(
+    schema: &Schema,
+    property: &str,
+    definitions: &Map<String, Schema>,
+    prefix: &str,
+) -> bool {
+    let schema = match schema {
+        Schema::Object(schema) => schema,
+        Schema::Bool(_) => return false,
+    };
+    if let Some(definition) = schema
+        .reference
+        .as_ref()
+        .and_then(|reference| reference.strip_prefix(prefix))
+        .and_then(|name| definitions.get(name))
+    {
+        return schema_has_property(definition, property, definitions, prefix);
+    }
+    if let Some(object) = &schema.object {
+        if object.properties.contains_key(property) {
+            return true;
+        }
+    }
+    match schema.subschemas.as_ref().and_then(|s| s.all_of.as_ref()) {
+        Some(all_of) => all_of
+            .iter()
+            .any(|schema| schema_has_property(schema, property, definitions, prefix)),
+        None => false,
+    }
+}
+
+fn without_title(schema: &Schema) -> Schema {
+    let mut schema = schema.clone();
+    if let Schema::Object(SchemaObject {
+        metadata: Some(metadata),
+        ..
+    }) = &mut schema
+    {
+        metadata.title = None;
+    }
+    schema
+}
+
+/// The position of the method in the `PathItem`, used to handle operations in a fixed order.
+fn method_order(method: Method) -> usize {
+    use Method::{Connect, Delete, Get, Head, Options, Patch, Post, Put, Trace};
+    match method {
+        Get => 0,
+        Put => 1,
+        Post => 2,
+        Delete => 3,
+        Options => 4,
+        Head => 5,
+        Patch => 6,
+        Trace => 7,
+        Connect => 8,
+    }
+}
+
+fn has_operation(path_item: &PathItem, method: Method) -> bool {
+    use Method::{Connect, Delete, Get, Head, Options, Patch, Post, Put, Trace};
+    match method {
+        Get => path_item.get.is_some(),
+        Put => path_item.put.is_some(),
+        Post => path_item.post.is_some(),
+        Delete => path_item.delete.is_some(),
+        Options => path_item.options.is_some(),
+        Head => path_item.head.is_some(),
+        Patch => path_item.patch.is_some(),
+        Trace => path_item.trace.is_some(),
+        Connect => path_item.extensions.contains_key("x-connect"),
+    }
+}
+
+fn set_operation(path_item: &mut PathItem, method: Method, op: Operation) {
+    use Method::{Connect, Delete, Get, Head, Options, Patch, Post, Put, Trace};
+    let option = match method {
+        Get => &mut path_item.get,
+        Put => &mut path_item.put,
+        Post => &mut path_item.post,
+        Delete => &mut path_item.delete,
+        Options => &mut path_item.options,
+        Head => &mut path_item.head,
+        Patch => &mut path_item.patch,
+        Trace => &mut path_item.trace,
+        // Connect not available in OpenAPI3, only added when `ConnectHandling::Extension` is used.
+        Connect => {
+            let value = serde_json::to_value(op).expect("Could not serialize Operation as JSON.");
+            path_item.extensions.insert("x-connect".to_owned(), value);
+            return;
+        }
+    };
+    assert!(option.is_none());
+    option.replace(op);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use okapi::openapi3::{ExampleValue, Info};
+
+    fn operation_info(path: &str, method: Method, summary: &str) -> OperationInfo {
+        OperationInfo {
+            path: path.to_owned(),
+            method,
+            operation: Operation {
+                summary: Some(summary.to_owned()),
+                ..Operation::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_try_add_operation_duplicate() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.try_add_operation(operation_info("/user", Method::Get, "a"))
+            .unwrap();
+        let err = gen
+            .try_add_operation(operation_info("/user", Method::Get, "b"))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            OpenApiError::DuplicateOperation { ref path, method: Method::Get } if path == "/user"
+        ));
+        assert_eq!(
+            err.to_string(),
+            "An operation for `GET /user` was already added."
+        );
+    }
+
+    #[test]
+    fn test_try_add_operation_identical() {
+        let settings = OpenApiSettings {
+            ignore_identical_operations: true,
+            ..OpenApiSettings::default()
+        };
+        let mut gen = OpenApiGenerator::new(&settings);
+        gen.try_add_operation(operation_info("/user", Method::Get, "a"))
+            .unwrap();
+        gen.try_add_operation(operation_info("/user", Method::Get, "a"))
+            .unwrap();
+        assert!(gen
+            .try_add_operation(operation_info("/user", Method::Get, "b"))
+            .is_err());
+    }
+
+    #[test]
+    fn test_connect_handling() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.try_add_operation(operation_info("/tunnel", Method::Connect, "a"))
+            .unwrap();
+        assert!(gen
+            .replace_operation(operation_info("/proxy", Method::Connect, "b"))
+            .is_none());
+        let (spec, warnings) = gen.into_openapi_with_warnings();
+        assert!(spec.paths.is_empty());
+        let warnings = warnings
+            .into_iter()
+            .filter(|warning| !matches!(warning, OpenApiWarning::EmptyInfo))
+            .collect::<Vec<_>>();
+        assert_eq!(warnings.len(), 2);
+        assert!(
+            matches!(&warnings[0], OpenApiWarning::DroppedConnect { path } if path == "/tunnel")
+        );
+        assert!(
+            matches!(&warnings[1], OpenApiWarning::DroppedConnect { path } if path == "/proxy")
+        );
+
+        let settings = OpenApiSettings {
+            connect_handling: ConnectHandling::Extension,
+            ..OpenApiSettings::default()
+        };
+        let mut gen = OpenApiGenerator::new(&settings);
+        gen.try_add_operation(operation_info("/tunnel", Method::Connect, "a"))
+            .unwrap();
+        let spec = gen.into_openapi();
+        assert_eq!(
+            spec.paths["/tunnel"].extensions["x-connect"],
+            serde_json::json!({"summary": "a", "responses": {}})
+        );
+
+        let settings = OpenApiSettings {
+            connect_handling: ConnectHandling::Error,
+            ..OpenApiSettings::default()
+        };
+        let mut gen = OpenApiGenerator::new(&settings);
+        assert!(matches!(
+            gen.try_add_operation(operation_info("/tunnel", Method::Connect, "a")),
+            Err(OpenApiError::UnsupportedMethod { .. })
+        ));
+        gen.replace_operation(operation_info("/proxy", Method::Connect, "b"));
+        assert!(matches!(
+            gen.take_warnings().as_slice(),
+            [OpenApiWarning::OperationError(OpenApiError::UnsupportedMethod { path, .. })]
+                if path == "/proxy"
+        ));
+        assert!(gen.into_openapi().paths.is_empty());
+    }
+
+    fn deterministic_spec_json() -> String {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        for path in ["/b", "/a", "/c"] {
+            for method in [Method::Post, Method::Get, Method::Delete, Method::Put] {
+                gen.add_operation(operation_info(path, method, path));
+            }
+        }
+        let foo = gen.json_schema::<FooResponse>();
+        let bar = gen.json_schema::<BarResponse>();
+        gen.add_operation(schema_operation_info("/foo", foo));
+        gen.add_operation(schema_operation_info("/bar", bar));
+        serde_json::to_string(&gen.into_openapi()).unwrap()
+    }
+
+    #[test]
+    fn test_deterministic_output() {
+        let json = deterministic_spec_json();
+        for _ in 0..5 {
+            assert_eq!(json, deterministic_spec_json());
+        }
+    }
+
+    #[test]
+    fn test_operation_id_fn() {
+        let to_camel_case = |op_id: &str| {
+            let mut parts = op_id.split("::").filter(|part| !part.is_empty());
+            let mut result = parts.next().unwrap_or_default().to_owned();
+            for part in parts {
+                let mut chars = part.chars();
+                result.extend(chars.next().map(|c| c.to_ascii_uppercase()));
+                result.push_str(chars.as_str());
+            }
+            result
+        };
+        let settings = OpenApiSettings {
+            operation_id_fn: Some(std::sync::Arc::new(to_camel_case)),
+            ..OpenApiSettings::default()
+        };
+        let mut gen = OpenApiGenerator::new(&settings);
+        let op = || {
+            let mut op = operation_info("/user", Method::Get, "a");
+            op.operation.operation_id = Some("my::module::handler".to_owned());
+            op
+        };
+        gen.add_operation(op());
+        // Replacing the operation does not apply the function twice.
+        gen.add_operation(op());
+        let spec = gen.into_openapi();
+        let op_id = spec.paths["/user"]
+            .get
+            .as_ref()
+            .unwrap()
+            .operation_id
+            .clone();
+        assert_eq!(op_id.as_deref(), Some("myModuleHandler"));
+    }
+
+    #[test]
+    fn test_add_tag() {
+        let tag = |name: &str, description: &str| Tag {
+            name: name.to_owned(),
+            description: Some(description.to_owned()),
+            ..Tag::default()
+        };
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_tag(tag("Users", "a"));
+        gen.add_tag(tag("Posts", "b"));
+        gen.add_tag(tag("Users", "c"));
+        assert_eq!(
+            gen.into_openapi().tags,
+            vec![tag("Users", "c"), tag("Posts", "b")]
+        );
+    }
+
+    fn bearer_requirement() -> SecurityRequirement {
+        let mut requirement = SecurityRequirement::new();
+        requirement.insert("bearer".to_owned(), vec![]);
+        requirement
+    }
+
+    #[test]
+    fn test_require_security() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_security_scheme(
+            "bearer".to_owned(),
+            SecurityScheme {
+                description: None,
+                data: okapi::openapi3::SecuritySchemeData::Http {
+                    scheme: "bearer".to_owned(),
+                    bearer_format: Some("JWT".to_owned()),
+                },
+                extensions: Default::default(),
+            },
+        );
+        gen.add_operation(operation_info("/user", Method::Get, "a"));
+        gen.require_security("/user", Method::Get, bearer_requirement())
+            .unwrap();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(
+            spec["components"]["securitySchemes"]["bearer"],
+            serde_json::json!({"type": "http", "scheme": "bearer", "bearerFormat": "JWT"})
+        );
+        assert_eq!(
+            spec["paths"]["/user"]["get"]["security"],
+            serde_json::json!([{"bearer": []}])
+        );
+    }
+
+    #[test]
+    fn test_default_security() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_default_security(bearer_requirement());
+        gen.add_operation(operation_info("/user", Method::Get, "a"));
+        let mut public = operation_info("/public", Method::Get, "b");
+        public.operation.security = Some(vec![]);
+        gen.add_operation(public);
+        let spec = gen.into_openapi();
+        let security = |path: &str| spec.paths[path].get.as_ref().unwrap().security.clone();
+        assert_eq!(security("/user"), Some(vec![bearer_requirement()]));
+        assert_eq!(security("/public"), Some(vec![]));
+    }
+
+    #[test]
+    fn test_set_global_security() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_security_scheme(
+            "bearer".to_owned(),
+            SecurityScheme {
+                description: None,
+                data: okapi::openapi3::SecuritySchemeData::Http {
+                    scheme: "bearer".to_owned(),
+                    bearer_format: None,
+                },
+                extensions: Default::default(),
+            },
+        );
+        gen.set_global_security(vec![bearer_requirement()]);
+        gen.add_operation(operation_info("/user", Method::Get, "a"));
+        let mut public = operation_info("/public", Method::Get, "b");
+        public.operation.security = Some(vec![]);
+        gen.add_operation(public);
+        let (spec, warnings) = gen.into_openapi_with_warnings();
+        assert!(!warnings
+            .iter()
+            .any(|warning| matches!(warning, OpenApiWarning::UnknownSecurityScheme { .. })));
+        let spec = serde_json::to_value(spec).unwrap();
+        assert_eq!(spec["security"], serde_json::json!([{"bearer": []}]));
+        assert!(spec["paths"]["/user"]["get"].get("security").is_none());
+        assert_eq!(
+            spec["paths"]["/public"]["get"]["security"],
+            serde_json::json!([])
+        );
+
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.set_global_security(vec![bearer_requirement()]);
+        let (_, warnings) = gen.into_openapi_with_warnings();
+        assert!(warnings.iter().any(|warning| matches!(
+            warning,
+            OpenApiWarning::UnknownSecurityScheme { name } if name == "bearer"
+        )));
+    }
+
+    fn parameter(name: &str, location: &str, schema: SchemaObject) -> Parameter {
+        Parameter {
+            name: name.to_owned(),
+            location: location.to_owned(),
+            description: None,
+            required: location == "path",
+            deprecated: false,
+            allow_empty_value: false,
+            value: ParameterValue::Schema {
+                style: None,
+                explode: None,
+                allow_reserved: false,
+                schema,
+                example: None,
+                examples: None,
+            },
+            extensions: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_add_string_enum_schema() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let countries = || vec!["FI".to_owned(), "SE".to_owned()];
+        let reference = match gen.add_string_enum_schema("CountryCode", countries()) {
+            RefOr::Ref(reference) => reference.reference,
+            RefOr::Object(_) => panic!("Expected a reference."),
+        };
+        assert_eq!(reference, "#/components/schemas/CountryCode");
+        gen.add_string_enum_schema("CountryCode", countries());
+        assert!(gen.warnings.is_empty());
+        gen.add_string_enum_schema("CountryCode", vec!["NO".to_owned()]);
+        assert!(matches!(
+            gen.warnings.as_slice(),
+            [OpenApiWarning::DuplicateSchemaName { name }] if name == "CountryCode"
+        ));
+
+        let mut op = operation_info("/users", Method::Get, "a");
+        op.operation
+            .parameters
+            .push(parameter("country", "query", SchemaObject::new_ref(reference)).into());
+        gen.add_operation(op);
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(
+            spec["components"]["schemas"]["CountryCode"],
+            serde_json::json!({"type": "string", "enum": ["FI", "SE"]})
+        );
+        assert_eq!(
+            spec["paths"]["/users"]["get"]["parameters"][0]["schema"],
+            serde_json::json!({"$ref": "#/components/schemas/CountryCode"})
+        );
+    }
+
+    #[test]
+    fn test_add_parameter_example() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let mut op = operation_info("/pets", Method::Get, "a");
+        let schema = gen.json_schema::<String>();
+        op.operation
+            .parameters
+            .push(parameter("filter", "query", schema).into());
+        gen.add_operation(op);
+        let example = |value: serde_json::Value| okapi::openapi3::Example {
+            summary: None,
+            description: None,
+            value: ExampleValue::Value(value),
+            extensions: Object::default(),
+        };
+        let by_name = example(serde_json::json!("name:rex"));
+        let by_age = example(serde_json::json!("age>2"));
+        gen.add_parameter_example("/pets", Method::Get, "filter", "by_name", by_name)
+            .unwrap();
+        gen.add_parameter_example("/pets", Method::Get, "filter", "by_age", by_age.clone())
+            .unwrap();
+        let missing = gen.add_parameter_example("/pets", Method::Get, "missing", "by_age", by_age);
+        assert!(missing.is_err());
+        assert!(gen.warnings().is_empty());
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(
+            spec["paths"]["/pets"]["get"]["parameters"][0]["examples"],
+            serde_json::json!({
+                "by_name": {"value": "name:rex"},
+                "by_age": {"value": "age>2"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_parameter_example_conflict() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let mut op = operation_info("/pets", Method::Get, "a");
+        let mut parameter = parameter("filter", "query", gen.json_schema::<String>());
+        if let ParameterValue::Schema { example, .. } = &mut parameter.value {
+            *example = Some(serde_json::json!("name:rex"));
+        }
+        op.operation.parameters.push(parameter.into());
+        gen.add_operation(op);
+        let by_age = okapi::openapi3::Example {
+            summary: None,
+            description: None,
+            value: ExampleValue::Value(serde_json::json!("age>2")),
+            extensions: Object::default(),
+        };
+        gen.add_parameter_example("/pets", Method::Get, "filter", "by_age", by_age)
+            .unwrap();
+        assert!(matches!(
+            gen.warnings(),
+            [OpenApiWarning::InvalidOperation(_)]
+        ));
+    }
+
+    #[test]
+    fn test_describe_parameter() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let mut op = operation_info("/user/{id}", Method::Get, "a");
+        let schema = gen.json_schema::<u64>();
+        op.operation
+            .parameters
+            .push(parameter("id", "path", schema).into());
+        gen.add_operation(op);
+        gen.describe_parameter(
+            "/user/{id}",
+            Method::Get,
+            "id",
+            Some("The id of the user.".to_owned()),
+            Some(serde_json::json!(42)),
+        )
+        .unwrap();
+        assert!(matches!(
+            gen.describe_parameter("/user/{id}", Method::Get, "name", None, None),
+            Err(OpenApiError::MissingParameter { .. })
+        ));
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let parameter = &spec["paths"]["/user/{id}"]["get"]["parameters"][0];
+        assert_eq!(parameter["description"], "The id of the user.");
+        assert_eq!(parameter["example"], 42);
+    }
+
+    #[test]
+    fn test_component_response() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_component_response(
+            "NotFound",
+            Response {
+                description: "The item was not found.".to_owned(),
+                ..Response::default()
+            },
+        );
+        gen.add_operation(operation_info("/user", Method::Get, "a"));
+        gen.add_operation(operation_info("/post", Method::Get, "b"));
+        gen.use_component_response("/user", Method::Get, 404, "NotFound")
+            .unwrap();
+        gen.use_component_response("/post", Method::Get, 404, "NotFound")
+            .unwrap();
+        let (spec, warnings) = gen.into_openapi_with_warnings();
+        assert!(!warnings
+            .iter()
+            .any(|warning| matches!(warning, OpenApiWarning::UnresolvedRef { .. })));
+        let spec = serde_json::to_value(spec).unwrap();
+        assert_eq!(
+            spec["components"]["responses"]["NotFound"]["description"],
+            "The item was not found."
+        );
+        for path in ["/user", "/post"] {
+            assert_eq!(
+                spec["paths"][path]["get"]["responses"]["404"]["$ref"],
+                "#/components/responses/NotFound"
+            );
+        }
+    }
+
+    #[test]
+    fn test_component_parameter() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let schema = gen.json_schema::<u32>();
+        gen.add_component_parameter("PageParam", parameter("page", "query", schema));
+        gen.add_operation(operation_info("/user", Method::Get, "a"));
+        gen.add_operation(operation_info("/post", Method::Get, "b"));
+        gen.use_component_parameter("/user", Method::Get, "PageParam")
+            .unwrap();
+        gen.use_component_parameter("/post", Method::Get, "PageParam")
+            .unwrap();
+        let (spec, warnings) = gen.into_openapi_with_warnings();
+        assert!(!warnings
+            .iter()
+            .any(|warning| matches!(warning, OpenApiWarning::UnresolvedRef { .. })));
+        let spec = serde_json::to_value(spec).unwrap();
+        assert_eq!(
+            spec["components"]["parameters"]["PageParam"]["name"],
+            "page"
+        );
+        for path in ["/user", "/post"] {
+            assert_eq!(
+                spec["paths"][path]["get"]["parameters"],
+                serde_json::json!([{"$ref": "#/components/parameters/PageParam"}])
+            );
+        }
+    }
+
+    #[test]
+    fn test_add_webhook() {
+        let webhook_spec = |settings: &OpenApiSettings| {
+            let mut gen = OpenApiGenerator::new(settings);
+            let schema = gen.json_schema::<Example>();
+            let mut operation = schema_operation_info("", schema).operation;
+            operation.summary = Some("A new order was placed.".to_owned());
+            gen.add_webhook(
+                "newOrder",
+                PathItem {
+                    post: Some(operation),
+                    ..PathItem::default()
+                },
+            );
+            serde_json::to_value(gen.into_openapi()).unwrap()
+        };
+        let spec = webhook_spec(&OpenApiSettings::default());
+        assert!(spec.get("webhooks").is_none());
+        assert_eq!(
+            spec["x-webhooks"]["newOrder"]["post"]["summary"],
+            "A new order was placed."
+        );
+        let spec = webhook_spec(&OpenApiSettings::default().with_version(OpenApiVersion::V3_1));
+        assert!(spec.get("x-webhooks").is_none());
+        assert_eq!(
+            spec["webhooks"]["newOrder"]["post"]["summary"],
+            "A new order was placed."
+        );
+    }
+
+    #[test]
+    fn test_add_callback() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/subscribe", Method::Post, "a"));
+        let mut callback = Callback::default();
+        callback.callbacks.insert(
+            "{$request.body#/callbackUrl}".to_owned(),
+            PathItem {
+                post: Some(operation_info("", Method::Post, "Notify").operation),
+                ..PathItem::default()
+            },
+        );
+        gen.add_callback("/subscribe", Method::Post, "onEvent", callback)
+            .unwrap();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(
+            spec["paths"]["/subscribe"]["post"]["callbacks"],
+            serde_json::json!({
+                "onEvent": {
+                    "{$request.body#/callbackUrl}": {
+                        "post": {"summary": "Notify", "responses": {}}
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_mark_deprecated() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/old", Method::Get, "a"));
+        gen.add_operation(operation_info("/new", Method::Get, "b"));
+        gen.mark_deprecated("/old", Method::Get, Some("2023-01-01".to_owned()))
+            .unwrap();
+        assert!(matches!(
+            gen.mark_deprecated("/missing", Method::Get, None),
+            Err(OpenApiError::MissingOperation { .. })
+        ));
+        let spec = gen.into_openapi();
+        let old = spec.paths["/old"].get.as_ref().unwrap();
+        assert!(old.deprecated);
+        assert_eq!(old.extensions["x-sunset"], "2023-01-01");
+        let new = spec.paths["/new"].get.as_ref().unwrap();
+        assert!(!new.deprecated);
+        assert!(new.extensions.is_empty());
+    }
+
+    #[test]
+    fn test_with_info() {
+        let info = okapi::openapi3::Info {
+            title: "My API".to_owned(),
+            version: "1.0.0".to_owned(),
+            terms_of_service: Some("https://example.com/terms".to_owned()),
+            contact: Some(okapi::openapi3::Contact {
+                name: Some("Support".to_owned()),
+                email: Some("support@example.com".to_owned()),
+                ..Default::default()
+            }),
+            license: Some(okapi::openapi3::License {
+                name: "MIT".to_owned(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let settings = OpenApiSettings::default().with_info(info.clone());
+        let spec = OpenApiGenerator::new(&settings).into_openapi();
+        assert_eq!(spec.info, info);
+        let json = serde_json::to_string(&spec).unwrap();
+        let spec: OpenApi = serde_json::from_str(&json).unwrap();
+        assert_eq!(spec.info, info);
+    }
+
+    #[test]
+    fn test_add_server() {
+        let mut variables = Map::new();
+        variables.insert(
+            "environment".to_owned(),
+            okapi::openapi3::ServerVariable {
+                enumeration: Some(vec!["staging".to_owned(), "production".to_owned()]),
+                default: "production".to_owned(),
+                description: Some("The environment".to_owned()),
+                ..Default::default()
+            },
+        );
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_server(Server {
+            url: "https://{environment}.api.example.com".to_owned(),
+            variables,
+            ..Server::default()
+        });
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(
+            spec["servers"],
+            serde_json::json!([{
+                "url": "https://{environment}.api.example.com",
+                "variables": {
+                    "environment": {
+                        "enum": ["staging", "production"],
+                        "default": "production",
+                        "description": "The environment"
+                    }
+                }
+            }])
+        );
+    }
+
+    mod first {
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        pub struct Same {
+            a: u32,
+        }
+    }
+
+    mod second {
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        pub struct Same {
+            b: String,
+        }
+    }
+
+    #[test]
+    fn test_duplicate_schema_name() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let first = gen.json_schema::<first::Same>();
+        assert_eq!(gen.json_schema::<first::Same>(), first);
+        let second = gen.json_schema::<second::Same>();
+        assert!(gen.warnings().is_empty());
+        assert_eq!(
+            first.reference.as_deref(),
+            Some("#/components/schemas/Same")
+        );
+        assert_eq!(
+            second.reference.as_deref(),
+            Some("#/components/schemas/Same2")
+        );
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let schemas = &spec["components"]["schemas"];
+        assert!(schemas["Same"]["properties"].get("a").is_some());
+        assert!(schemas["Same2"]["properties"].get("b").is_some());
+    }
+
+    #[test]
+    fn test_external_docs() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        assert!(gen.set_external_docs("", None).is_err());
+        gen.set_external_docs("https://example.com/guide", Some("Guide".to_owned()))
+            .unwrap();
+        assert!(gen
+            .set_operation_external_docs("/users", Method::Get, "https://example.com", None)
+            .is_err());
+        gen.add_operation(operation_info("/users", Method::Get, "get_users"));
+        assert!(gen
+            .set_operation_external_docs("/users", Method::Get, " ", None)
+            .is_err());
+        gen.set_operation_external_docs("/users", Method::Get, "https://example.com/users", None)
+            .unwrap();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(
+            spec["externalDocs"],
+            serde_json::json!({"url": "https://example.com/guide", "description": "Guide"})
+        );
+        assert_eq!(
+            spec["paths"]["/users"]["get"]["externalDocs"],
+            serde_json::json!({"url": "https://example.com/users"})
+        );
+    }
+
+    #[test]
+    fn test_into_openapi_strict() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/users", Method::Get, "get_users"));
+        gen.use_component_parameter("/users", Method::Get, "Missing")
+            .unwrap();
+        let errors = gen.into_openapi_strict().unwrap_err();
+        assert!(matches!(
+            errors.as_slice(),
+            [
+                OpenApiError::EmptyInfoField { field: "title" },
+                OpenApiError::EmptyInfoField { field: "version" },
+                OpenApiError::MissingResponses { path, method: Method::Get },
+                OpenApiError::UnresolvedRef { reference },
+            ] if path == "/users" && reference == "#/components/parameters/Missing"
+        ));
+
+        let info = Info {
+            title: "Title".to_owned(),
+            version: "1.0.0".to_owned(),
+            ..Info::default()
+        };
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default().with_info(info));
+        gen.add_operation(operation_info("/users", Method::Get, "get_users"));
+        gen.add_response_content(
+            "/users",
+            Method::Get,
+            200,
+            "text/plain",
+            SchemaObject::default(),
+        )
+        .unwrap();
+        assert!(gen.into_openapi_strict().is_ok());
+    }
+
+    #[test]
+    fn test_into_openapi_strict_injected_responses() {
+        let info = Info {
+            title: "Title".to_owned(),
+            version: "1.0.0".to_owned(),
+            ..Info::default()
+        };
+        let not_found = Response {
+            description: "Not found.".to_owned(),
+            ..Response::default()
+        };
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default().with_info(info));
+        gen.add_operation(operation_info("/users", Method::Get, "get_users"));
+        gen.add_catcher_response(404, not_found);
+        assert!(gen.into_openapi_strict().is_ok());
+    }
+
+    #[test]
+    fn test_into_openapi_strict_duplicate_operation() {
+        let errors = collision_gen(CollisionPolicy::Error)
+            .into_openapi_strict()
+            .unwrap_err();
+        assert!(errors.iter().any(|error| matches!(
+            error,
+            OpenApiError::DuplicateOperation { path, method: Method::Get } if path == "/user"
+        )));
+    }
+
+    #[test]
+    fn test_into_openapi_strict_unsupported_method() {
+        let settings = OpenApiSettings {
+            connect_handling: ConnectHandling::Error,
+            ..OpenApiSettings::default()
+        };
+        let mut gen = OpenApiGenerator::new(&settings);
+        gen.add_operation(operation_info("/tunnel", Method::Connect, "tunnel"));
+        let errors = gen.into_openapi_strict().unwrap_err();
+        assert!(errors.iter().any(|error| matches!(
+            error,
+            OpenApiError::UnsupportedMethod { path, method: Method::Connect } if path == "/tunnel"
+        )));
+    }
+
+    #[test]
+    fn test_into_openapi_strict_invalid_server() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let server = server_with_variable(Some(vec!["api"]), "dev");
+        let expected = validate_server(&server).unwrap_err().to_string();
+        gen.add_server(server);
+        let errors = gen.into_openapi_strict().unwrap_err();
+        assert!(errors.iter().any(|error| error.to_string() == expected));
+    }
+
+    #[test]
+    fn test_into_openapi_strict_invalid_example() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let schema = gen.json_schema::<Example>();
+        gen.add_operation(schema_operation_info("/example", schema));
+        gen.add_response_example("/example", Method::Get, 200, &"text")
+            .unwrap();
+        let errors = gen.into_openapi_strict().unwrap_err();
+        assert!(errors.iter().any(|error| matches!(
+            error,
+            OpenApiError::InvalidExample { path, method: Method::Get, content_type }
+                if path == "/example" && content_type == "application/json"
+        )));
+    }
+
+    #[test]
+    fn test_set_path_servers() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/legacy", Method::Get, "get_legacy"));
+        gen.add_operation(operation_info("/legacy", Method::Post, "post_legacy"));
+        let server = Server {
+            url: "https://legacy.example.com".to_owned(),
+            ..Server::default()
+        };
+        gen.set_path_servers("/legacy", vec![server]);
+        gen.set_path_servers("/unused", vec![Server::default()]);
+        let spec = gen.into_openapi();
+        assert!(!spec.paths.contains_key("/unused"));
+        let path_item = &spec.paths["/legacy"];
+        assert!(path_item.get.is_some() && path_item.post.is_some());
+        let servers = path_item.servers.as_ref().unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].url, "https://legacy.example.com");
+    }
+
+    #[test]
+    fn test_hoist_path_parameters() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let id = parameter("id", "path", gen.json_schema::<u64>());
+        let verbose = parameter("verbose", "query", gen.json_schema::<bool>());
+        for (method, params) in [
+            (Method::Get, vec![id.clone(), verbose]),
+            (Method::Delete, vec![id.clone()]),
+        ] {
+            let mut op = operation_info("/users/{id}", method, "a");
+            op.operation.parameters = params.into_iter().map(RefOr::Object).collect();
+            gen.add_operation(op);
+        }
+        let mut single = operation_info("/users/{id}/avatar", Method::Get, "b");
+        single.operation.parameters.push(id.clone().into());
+        gen.add_operation(single);
+        gen.set_path_parameters("/unused", vec![id.clone().into()]);
+        gen.hoist_path_parameters();
+        assert!(gen.validate_path_params().is_empty());
+        let spec = gen.into_openapi();
+        assert!(!spec.paths.contains_key("/unused"));
+        let path_item = &spec.paths["/users/{id}"];
+        assert_eq!(path_item.parameters, vec![RefOr::Object(id)]);
+        let get = path_item.get.as_ref().unwrap();
+        let names: Vec<_> = get
+            .parameters
+            .iter()
+            .map(|param| match param {
+                RefOr::Object(param) => param.name.as_str(),
+                RefOr::Ref(_) => panic!("Expected a parameter."),
+            })
+            .collect();
+        assert_eq!(names, vec!["verbose"]);
+        assert!(path_item.delete.as_ref().unwrap().parameters.is_empty());
+        let avatar = &spec.paths["/users/{id}/avatar"];
+        assert!(avatar.parameters.is_empty());
+        assert_eq!(avatar.get.as_ref().unwrap().parameters.len(), 1);
+    }
+
+    #[test]
+    fn test_set_operation_servers() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/files", Method::Get, "get_files"));
+        gen.add_operation(operation_info("/files", Method::Post, "post_files"));
+        let server = |url: &str| Server {
+            url: url.to_owned(),
+            ..Server::default()
+        };
+        gen.set_path_servers("/files", vec![server("https://api.example.com")]);
+        gen.set_operation_servers(
+            "/files",
+            Method::Post,
+            vec![server("https://upload.example.com")],
+        )
+        .unwrap();
+        assert!(gen
+            .set_operation_servers("/files", Method::Delete, vec![])
+            .is_err());
+        let spec = gen.into_openapi();
+        let path_item = &spec.paths["/files"];
+        assert_eq!(
+            path_item.servers.as_ref().unwrap()[0].url,
+            "https://api.example.com"
+        );
+        let post_servers = path_item.post.as_ref().unwrap().servers.as_ref().unwrap();
+        assert_eq!(post_servers[0].url, "https://upload.example.com");
+        assert!(path_item.get.as_ref().unwrap().servers.is_none());
+    }
+
+    #[test]
+    fn test_add_named_example() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/users", Method::Get, "get_users"));
+        let example = |value: serde_json::Value| okapi::openapi3::Example {
+            summary: None,
+            description: None,
+            value: ExampleValue::Value(value),
+            extensions: Object::default(),
+        };
+        for (name, value) in [("one", 1), ("two", 2)].iter() {
+            gen.add_named_example(
+                "/users",
+                Method::Get,
+                200,
+                "application/json",
+                name,
+                example(serde_json::json!(value)),
+            )
+            .unwrap();
+        }
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(
+            spec["paths"]["/users"]["get"]["responses"]["200"]["content"]["application/json"],
+            serde_json::json!({
+                "examples": {"one": {"value": 1}, "two": {"value": 2}}
+            })
+        );
+    }
+
+    #[test]
+    fn test_add_operation_extension() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/users", Method::Get, "get_users"));
+        let integration = serde_json::json!({"type": "http_proxy", "httpMethod": "GET"});
+        assert!(gen
+            .add_operation_extension("/users", Method::Get, "integration", integration.clone())
+            .is_err());
+        gen.add_operation_extension(
+            "/users",
+            Method::Get,
+            "x-amazon-apigateway-integration",
+            integration.clone(),
+        )
+        .unwrap();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let operation = &spec["paths"]["/users"]["get"];
+        assert_eq!(operation["x-amazon-apigateway-integration"], integration);
+        assert!(operation.get("integration").is_none());
+    }
+
+    #[test]
+    fn test_add_root_extension() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let logo = serde_json::json!({"url": "https://example.com/logo.png"});
+        assert!(gen.add_root_extension("logo", logo.clone()).is_err());
+        gen.add_root_extension("x-logo", logo.clone()).unwrap();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(spec["x-logo"], logo);
+        assert!(spec.get("logo").is_none());
+    }
+
+    #[test]
+    fn test_json_schemas_parallel() {
+        let mut sequential = OpenApiGenerator::new(&OpenApiSettings::default());
+        let schemas = vec![
+            sequential.json_schema::<FooResponse>(),
+            sequential.json_schema::<InnerA>(),
+            sequential.json_schema::<BarResponse>(),
+            sequential.json_schema::<InnerB>(),
+            sequential.json_schema::<TreeNode>(),
+            sequential.json_schema::<Parent>(),
+            sequential.json_schema::<u32>(),
+        ];
+        let types = vec![
+            ParallelSchema::of::<FooResponse>(),
+            ParallelSchema::of::<InnerA>(),
+            ParallelSchema::of::<BarResponse>(),
+            ParallelSchema::of::<InnerB>(),
+            ParallelSchema::of::<TreeNode>(),
+            ParallelSchema::of::<Parent>(),
+            ParallelSchema::of::<u32>(),
+        ];
+        let mut parallel = OpenApiGenerator::new(&OpenApiSettings::default());
+        assert_eq!(parallel.json_schemas_parallel(types.clone()), schemas);
+        let mut parallel = OpenApiGenerator::new(&OpenApiSettings::default());
+        assert_eq!(parallel.json_schemas_on_threads(types, 2), (schemas, true));
+        assert!(parallel.warnings().is_empty());
+        assert_eq!(
+            serde_json::to_string(&parallel.into_openapi()).unwrap(),
+            serde_json::to_string(&sequential.into_openapi()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_json_schemas_parallel_name_collision() {
+        let mut sequential = OpenApiGenerator::new(&OpenApiSettings::default());
+        let schemas = vec![
+            sequential.json_schema::<first::Same>(),
+            sequential.json_schema::<second::Same>(),
+        ];
+        // Use two threads, so the types are in different chunks.
+        let types = vec![
+            ParallelSchema::of::<first::Same>(),
+            ParallelSchema::of::<second::Same>(),
+        ];
+        let mut parallel = OpenApiGenerator::new(&OpenApiSettings::default());
+        assert_eq!(parallel.json_schemas_on_threads(types, 2).0, schemas);
+        assert_eq!(
+            schemas[1].reference.as_deref(),
+            Some("#/components/schemas/Same2")
+        );
+        assert_eq!(
+            serde_json::to_string(&parallel.into_openapi()).unwrap(),
+            serde_json::to_string(&sequential.into_openapi()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_json_schemas_parallel_schema_naming() {
+        let settings = OpenApiSettings {
+            schema_naming: crate::settings::SchemaNaming::FullPath,
+            ..OpenApiSettings::default()
+        };
+        let mut sequential = OpenApiGenerator::new(&settings);
+        let schemas = vec![
+            sequential.json_schema::<mod_a::Config>(),
+            sequential.json_schema::<mod_b::Config>(),
+            sequential.json_schema::<ConfigWrapper>(),
+        ];
+        let types = vec![
+            ParallelSchema::of::<mod_a::Config>(),
+            ParallelSchema::of::<mod_b::Config>(),
+            ParallelSchema::of::<ConfigWrapper>(),
+        ];
+        let mut parallel = OpenApiGenerator::new(&settings);
+        assert_eq!(
+            parallel.json_schemas_on_threads(types, 2),
+            (schemas.clone(), true)
+        );
+        assert_eq!(parallel.definitions(), sequential.definitions());
+        assert_eq!(
+            schemas[0].reference.as_deref(),
+            Some("#/components/schemas/rocket_okapi.gen.test.mod_a.Config")
+        );
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct WithFirst {
+        same: first::Same,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct WithSecond {
+        same: second::Same,
+    }
+
+    #[test]
+    fn test_json_schemas_parallel_nested_name_collision() {
+        let mut sequential = OpenApiGenerator::new(&OpenApiSettings::default());
+        sequential.json_schema::<WithFirst>();
+        sequential.json_schema::<first::Same>();
+        sequential.json_schema::<WithSecond>();
+        let expected = sequential.into_openapi();
+
+        // The nested type is not in the types, so the generator can not learn its name.
+        let mut parallel = OpenApiGenerator::new(&OpenApiSettings::default());
+        let types = vec![ParallelSchema::of::<WithFirst>()];
+        assert!(!parallel.json_schemas_on_threads(types, 2).1);
+        parallel.json_schema::<first::Same>();
+        parallel.json_schema::<WithSecond>();
+        assert_eq!(
+            serde_json::to_string(&parallel.into_openapi()).unwrap(),
+            serde_json::to_string(&expected).unwrap()
+        );
+
+        let mut parallel = OpenApiGenerator::new(&OpenApiSettings::default());
+        let types = vec![
+            ParallelSchema::of::<WithFirst>(),
+            ParallelSchema::of::<first::Same>(),
+        ];
+        assert!(parallel.json_schemas_on_threads(types, 2).1);
+        parallel.json_schema::<WithSecond>();
+        let spec = serde_json::to_value(parallel.into_openapi()).unwrap();
+        let schemas = &spec["components"]["schemas"];
+        assert_eq!(
+            schemas["WithSecond"]["properties"]["same"]["$ref"],
+            "#/components/schemas/Same2"
+        );
+        assert!(schemas["Same2"]["properties"].get("b").is_some());
+        assert!(schemas["Same"]["properties"].get("a").is_some());
+        assert_eq!(spec, serde_json::to_value(&expected).unwrap());
+
+        // The generator already named a different type `Same`, the definition is gone.
+        let mut parallel = OpenApiGenerator::new(&OpenApiSettings::default());
+        parallel.json_schema::<first::Same>();
+        parallel
+            .schema_generator_mut()
+            .definitions_mut()
+            .remove("Same");
+        let types = vec![ParallelSchema::of::<second::Same>()];
+        let (schemas, used) = parallel.json_schemas_on_threads(types, 2);
+        assert!(!used);
+        assert_eq!(
+            schemas[0].reference.as_deref(),
+            Some("#/components/schemas/Same2")
+        );
+        let definitions = parallel.definitions();
+        assert_eq!(definitions.keys().collect::<Vec<_>>(), vec!["Same2"]);
+    }
+
+    #[test]
+    fn test_json_request_body() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        assert!(gen
+            .json_request_body::<InnerA>("/users", Method::Post, true, None)
+            .is_err());
+        assert!(gen.definitions().is_empty());
+        gen.add_operation(operation_info("/users", Method::Post, "post_users"));
+        gen.add_request_content(
+            "/users",
+            Method::Post,
+            "text/plain",
+            SchemaObject::default(),
+        )
+        .unwrap();
+        gen.json_request_body::<InnerA>(
+            "/users",
+            Method::Post,
+            true,
+            Some("The new user.".to_owned()),
+        )
+        .unwrap();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(
+            spec["paths"]["/users"]["post"]["requestBody"],
+            serde_json::json!({
+                "description": "The new user.",
+                "content": {
+                    "application/json": {
+                        "schema": {"$ref": "#/components/schemas/InnerA"}
+                    }
+                },
+                "required": true
+            })
+        );
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct AuditFields {
+        created_at: String,
+        updated_at: String,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Article {
+        title: String,
+        #[serde(flatten)]
+        audit: AuditFields,
+    }
+
+    #[test]
+    fn test_compose_all_of() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.json_schema::<Article>();
+        assert!(gen.compose_all_of("Article", &["AuditFields"]).is_err());
+        gen.json_schema::<AuditFields>();
+        assert!(gen.compose_all_of("Missing", &["AuditFields"]).is_err());
+        gen.compose_all_of("Article", &["AuditFields"]).unwrap();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(
+            spec["components"]["schemas"]["Article"],
+            serde_json::json!({
+                "allOf": [
+                    {"$ref": "#/components/schemas/AuditFields"},
+                    {
+                        "type": "object",
+                        "required": ["title"],
+                        "properties": {"title": {"type": "string"}}
+                    }
+                ]
+            })
+        );
+    }
+
+    fn operation_with_id(path: &str, operation_id: &str) -> OperationInfo {
+        OperationInfo {
+            path: path.to_owned(),
+            method: Method::Get,
+            operation: Operation {
+                operation_id: Some(operation_id.to_owned()),
+                ..Operation::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_unique_operation_ids() {
+        let add_operations = |gen: &mut OpenApiGenerator| {
+            // `a::handler` and `a_handler` both become `a_handler`.
+            gen.add_operation(operation_with_id("/one", "a::handler"));
+            gen.add_operation(operation_with_id("/two", "a_handler"));
+            gen.add_operation(operation_with_id("/three", "a_handler_2"));
+        };
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        add_operations(&mut gen);
+        let (spec, warnings) = gen.into_openapi_with_warnings();
+        let ids: Vec<_> = spec
+            .paths
+            .values()
+            .map(|item| item.get.as_ref().unwrap().operation_id.clone().unwrap())
+            .collect();
+        assert_eq!(ids, vec!["a_handler", "a_handler_3", "a_handler_2"]);
+        assert!(!warnings
+            .iter()
+            .any(|warning| matches!(warning, OpenApiWarning::InvalidOperation(_))));
+
+        let settings = OpenApiSettings {
+            operation_id_collision: OperationIdCollision::Error,
+            ..OpenApiSettings::default()
+        };
+        let mut gen = OpenApiGenerator::new(&settings);
+        add_operations(&mut gen);
+        let errors = gen.into_openapi_strict().unwrap_err();
+        assert!(errors.iter().any(|error| matches!(
+            error,
+            OpenApiError::DuplicateOperationId { operation_id } if operation_id == "a_handler"
+        )));
+    }
+
+    #[test]
+    fn test_validate_path_params() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let mut info = operation_info("/users/{id}", Method::Get, "get_user");
+        info.operation
+            .parameters
+            .push(parameter("user_id", "path", SchemaObject::default()).into());
+        gen.add_operation(info);
+        gen.add_operation(operation_info("/users", Method::Get, "get_users"));
+        let errors = gen.validate_path_params();
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(
+            &errors[0],
+            OpenApiError::MissingPathParameter { path, method: Method::Get, name }
+                if path == "/users/{id}" && name == "id"
+        ));
+        assert!(matches!(
+            &errors[1],
+            OpenApiError::UnknownPathParameter { name, .. } if name == "user_id"
+        ));
+        let (_, warnings) = gen.into_openapi_with_warnings();
+        let operation_errors = warnings
+            .iter()
+            .filter(|warning| matches!(warning, OpenApiWarning::InvalidOperation(_)))
+            .count();
+        assert_eq!(operation_errors, 2);
+    }
+
+    #[test]
+    fn test_set_parameter_serialization() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let mut info = operation_info("/posts/{id}", Method::Get, "get_posts");
+        info.operation.parameters = vec![
+            parameter("id", "path", SchemaObject::default()).into(),
+            parameter("tags", "query", SchemaObject::default()).into(),
+        ];
+        gen.add_operation(info);
+        assert!(gen
+            .set_parameter_serialization(
+                "/posts/{id}",
+                Method::Get,
+                "id",
+                ParameterStyle::PipeDelimited,
+                false
+            )
+            .is_err());
+        gen.set_parameter_serialization(
+            "/posts/{id}",
+            Method::Get,
+            "tags",
+            ParameterStyle::PipeDelimited,
+            false,
+        )
+        .unwrap();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let tags = &spec["paths"]["/posts/{id}"]["get"]["parameters"][1];
+        assert_eq!(tags["style"], "pipeDelimited");
+        assert_eq!(tags["explode"], false);
+        let id = &spec["paths"]["/posts/{id}"]["get"]["parameters"][0];
+        assert!(id.get("style").is_none());
+    }
+
+    #[test]
+    fn test_header_parameter() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/users", Method::Get, "get_users"));
+        let header = gen.header_for::<String>(Some("Id of the request.".to_owned()), true);
+        gen.add_header_parameter("/users", Method::Get, "X-Request-Id", header)
+            .unwrap();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(
+            spec["paths"]["/users"]["get"]["parameters"][0],
+            serde_json::json!({
+                "name": "X-Request-Id",
+                "in": "header",
+                "description": "Id of the request.",
+                "required": true,
+                "schema": {"type": "string"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_response_header() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/users", Method::Get, "get_users"));
+        let header = gen.header_for::<u32>(None, false);
+        gen.add_response_header("/users", Method::Get, 200, "X-RateLimit-Remaining", header)
+            .unwrap();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(
+            spec["paths"]["/users"]["get"]["responses"]["200"]["headers"],
+            serde_json::json!({
+                "X-RateLimit-Remaining": {
+                    "schema": {"type": "integer", "format": "uint32", "minimum": 0.0}
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_add_response_link() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_with_id("/orders", "create_order"));
+        gen.add_operation(operation_with_id("/orders/{id}", "get_order"));
+        let mut parameters = Map::new();
+        parameters.insert("id".to_owned(), serde_json::json!("$response.body#/id"));
+        let link = Link {
+            operation_id: Some("get_order".to_owned()),
+            parameters,
+            ..Link::default()
+        };
+        gen.add_response_link("/orders", Method::Get, 201, "GetOrder", link)
+            .unwrap();
+        let unknown = Link {
+            operation_id: Some("delete_order".to_owned()),
+            ..Link::default()
+        };
+        gen.add_response_link("/orders", Method::Get, 201, "DeleteOrder", unknown)
+            .unwrap();
+        let (spec, warnings) = gen.into_openapi_with_warnings();
+        let spec = serde_json::to_value(spec).unwrap();
+        assert_eq!(
+            spec["paths"]["/orders"]["get"]["responses"]["201"]["links"]["GetOrder"],
+            serde_json::json!({
+                "operationId": "get_order",
+                "parameters": {"id": "$response.body#/id"}
+            })
+        );
+        let unknown_links: Vec<_> = warnings
+            .iter()
+            .filter_map(|warning| match warning {
+                OpenApiWarning::InvalidOperation(OpenApiError::UnknownLinkOperation {
+                    operation_id,
+                    ..
+                }) => Some(operation_id.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(unknown_links, vec!["delete_order"]);
+    }
+
+    #[test]
+    fn test_default_response() {
+        let response = Response {
+            description: "Something went wrong.".to_owned(),
+            ..Response::default()
+        };
+        let settings = OpenApiSettings {
+            default_response: Some(("default".to_owned(), response)),
+            ..OpenApiSettings::default()
+        };
+        let mut gen = OpenApiGenerator::new(&settings);
+        gen.add_operation(operation_info("/any", Method::Get, "catch_all"));
+        gen.add_operation(operation_info("/users", Method::Get, "get_users"));
+        gen.add_response_content(
+            "/users",
+            Method::Get,
+            200,
+            "text/plain",
+            SchemaObject::default(),
+        )
+        .unwrap();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(
+            spec["paths"]["/any"]["get"]["responses"],
+            serde_json::json!({"default": {"description": "Something went wrong."}})
+        );
+        assert!(spec["paths"]["/users"]["get"]["responses"]
+            .get("default")
+            .is_none());
+    }
+
+    #[test]
+    fn test_add_catcher_response() {
+        let not_found = |description: &str| Response {
+            description: description.to_owned(),
+            ..Response::default()
+        };
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/users", Method::Get, "get_users"));
+        gen.add_operation(operation_info("/users/{id}", Method::Get, "get_user"));
+        gen.add_response_content(
+            "/users/{id}",
+            Method::Get,
+            404,
+            "text/plain",
+            SchemaObject::default(),
+        )
+        .unwrap();
+        gen.add_catcher_response(404, not_found("Not found."));
+        let spec = serde_json::to_value(gen.clone().into_openapi()).unwrap();
+        assert_eq!(
+            spec["paths"]["/users"]["get"]["responses"]["404"],
+            serde_json::json!({"description": "Not found."})
+        );
+        assert!(
+            spec["paths"]["/users/{id}"]["get"]["responses"]["404"]["content"]
+                .get("text/plain")
+                .is_some()
+        );
+
+        let settings = OpenApiSettings {
+            inject_catcher_responses: false,
+            ..OpenApiSettings::default()
+        };
+        let mut gen = OpenApiGenerator::new(&settings);
+        gen.add_operation(operation_info("/users", Method::Get, "get_users"));
+        gen.add_catcher_response(404, not_found("Not found."));
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert!(spec["paths"]["/users"]["get"]["responses"]
+            .get("404")
+            .is_none());
+        assert_eq!(
+            spec["components"]["responses"]["404"],
+            serde_json::json!({"description": "Not found."})
+        );
+    }
+
+    mod mod_a {
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        pub struct Config {
+            pub name: String,
+        }
+    }
+
+    mod mod_b {
+        #[derive(schemars::JsonSchema)]
+        #[allow(dead_code)]
+        pub struct Config {
+            pub port: u16,
+        }
+    }
+
+    #[test]
+    fn test_schema_naming_full_path() {
+        let settings = OpenApiSettings {
+            schema_naming: crate::settings::SchemaNaming::FullPath,
+            ..OpenApiSettings::default()
+        };
+        let mut gen = OpenApiGenerator::new(&settings);
+        gen.add_operation(operation_info("/a", Method::Get, "a"));
+        gen.add_operation(operation_info("/b", Method::Get, "b"));
+        let schema_a = gen.json_schema::<mod_a::Config>();
+        let schema_b = gen.json_schema::<mod_b::Config>();
+        gen.add_response_content("/a", Method::Get, 200, "application/json", schema_a)
+            .unwrap();
+        gen.add_response_content("/b", Method::Get, 200, "application/json", schema_b)
+            .unwrap();
+        assert!(gen.warnings.is_empty());
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let key_a = "rocket_okapi.gen.test.mod_a.Config";
+        let key_b = "rocket_okapi.gen.test.mod_b.Config";
+        let schemas = spec["components"]["schemas"].as_object().unwrap();
+        assert_eq!(schemas.keys().collect::<Vec<_>>(), vec![key_a, key_b]);
+        assert!(schemas[key_a]["properties"].get("name").is_some());
+        assert!(schemas[key_b]["properties"].get("port").is_some());
+        let reference = |path: &str| {
+            spec["paths"][path]["get"]["responses"]["200"]["content"]["application/json"]["schema"]
+                ["$ref"]
+                .clone()
+        };
+        assert_eq!(reference("/a"), format!("#/components/schemas/{}", key_a));
+        assert_eq!(reference("/b"), format!("#/components/schemas/{}", key_b));
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct ConfigWrapper {
+        config: mod_a::Config,
+        other: mod_b::Config,
+    }
+
+    #[test]
+    fn test_schema_naming_when_generated() {
+        let settings = OpenApiSettings {
+            schema_naming: crate::settings::SchemaNaming::FullPath,
+            ..OpenApiSettings::default()
+        };
+        let mut gen = OpenApiGenerator::new(&settings);
+        let key_a = "rocket_okapi.gen.test.mod_a.Config";
+        let key_wrapper = "rocket_okapi.gen.test.ConfigWrapper";
+        let schema = gen.json_schema::<mod_a::Config>();
+        let reference = format!("#/components/schemas/{}", key_a);
+        assert_eq!(schema.reference.as_ref(), Some(&reference));
+        assert_eq!(gen.definitions().keys().collect::<Vec<_>>(), vec![key_a]);
+        gen.deprecate_property(key_a, "name").unwrap();
+
+        // `mod_a::Config` keeps its new name, `mod_b::Config` is only used by a field.
+        let schema = gen.json_schema::<ConfigWrapper>();
+        assert_eq!(
+            schema.reference,
+            Some(format!("#/components/schemas/{}", key_wrapper))
+        );
+        assert_eq!(
+            gen.definitions().keys().collect::<Vec<_>>(),
+            vec![key_a, key_wrapper, "Config2"]
+        );
+        let wrapper = serde_json::to_value(&gen.definitions()[key_wrapper]).unwrap();
+        assert_eq!(
+            wrapper["properties"]["config"]["$ref"],
+            serde_json::json!(reference)
+        );
+        assert_eq!(
+            wrapper["properties"]["other"]["$ref"],
+            "#/components/schemas/Config2"
+        );
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let schemas = &spec["components"]["schemas"];
+        assert_eq!(schemas[key_a]["properties"]["name"]["deprecated"], true);
+        assert!(schemas["Config2"]["properties"].get("port").is_some());
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    #[schemars(title = "Account")]
+    struct Profile {
+        name: String,
+    }
+
+    #[test]
+    fn test_schema_titles_from_name() {
+        let settings = OpenApiSettings {
+            schema_titles_from_name: true,
+            ..OpenApiSettings::default()
+        };
+        let mut gen = OpenApiGenerator::new(&settings);
+        gen.json_schema::<NewUser>();
+        gen.json_schema::<Profile>();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let schemas = &spec["components"]["schemas"];
+        assert_eq!(schemas["NewUser"]["title"], "NewUser");
+        assert_eq!(schemas["Profile"]["title"], "Account");
+    }
+
+    #[test]
+    fn test_add_sse_response() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/events", Method::Get, "events"));
+        let event = gen.json_schema::<NewUser>();
+        gen.add_sse_response("/events", Method::Get, RefOr::Object(event))
+            .unwrap();
+        assert!(gen
+            .add_sse_response(
+                "/missing",
+                Method::Get,
+                RefOr::Object(SchemaObject::default())
+            )
+            .is_err());
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(
+            spec["paths"]["/events"]["get"]["responses"]["200"]["content"],
+            serde_json::json!({"text/event-stream": {
+                "schema": {"$ref": "#/components/schemas/NewUser"},
+                "x-sse": true
+            }})
+        );
+    }
+
+    #[test]
+    fn test_trailing_slash() {
+        let paths = |trailing_slash: TrailingSlash| {
+            let settings = OpenApiSettings {
+                trailing_slash,
+                ..OpenApiSettings::default()
+            };
+            let mut gen = OpenApiGenerator::new(&settings);
+            gen.add_operation(operation_info("/users", Method::Get, "list"));
+            gen.add_operation(operation_info("/users/", Method::Post, "create"));
+            gen.add_operation(operation_info("//users//{id}", Method::Get, "get"));
+            gen.add_operation(operation_info("/", Method::Get, "index"));
+            let spec = gen.into_openapi();
+            let paths: Vec<(String, bool, bool)> = spec
+                .paths
+                .into_iter()
+                .map(|(path, item)| (path, item.get.is_some(), item.post.is_some()))
+                .collect();
+            paths
+        };
+        assert_eq!(
+            paths(TrailingSlash::Keep),
+            vec![
+                ("/users".to_owned(), true, false),
+                ("/users/".to_owned(), false, true),
+                ("/users/{id}".to_owned(), true, false),
+                ("/".to_owned(), true, false),
+            ]
+        );
+        assert_eq!(
+            paths(TrailingSlash::Strip),
+            vec![
+                ("/users".to_owned(), true, true),
+                ("/users/{id}".to_owned(), true, false),
+                ("/".to_owned(), true, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_trailing_slash_lookups() {
+        let settings = OpenApiSettings {
+            trailing_slash: TrailingSlash::Strip,
+            ..OpenApiSettings::default()
+        };
+        let mut gen = OpenApiGenerator::new(&settings);
+        gen.add_operation(operation_info("/users", Method::Get, "list"));
+        let replaced = gen.replace_operation(operation_info("/users/", Method::Get, "replaced"));
+        assert_eq!(replaced.unwrap().summary.as_deref(), Some("list"));
+        gen.add_operation(operation_info("/users/{id}", Method::Get, "get"));
+        gen.mark_deprecated("//users//{id}/", Method::Get, None)
+            .unwrap();
+        gen.add_operation(operation_info("/pets", Method::Get, "pets"));
+        assert!(gen.remove_operation("//pets/", Method::Get).is_some());
+        let spec = gen.into_openapi();
+        let paths: Vec<&str> = spec.paths.keys().map(String::as_str).collect();
+        assert_eq!(paths, vec!["/users", "/users/{id}"]);
+        let get = |path: &str| spec.paths[path].get.as_ref().unwrap();
+        assert_eq!(get("/users").summary.as_deref(), Some("replaced"));
+        assert!(get("/users/{id}").deprecated);
+    }
+
+    fn example_coupon() -> Coupon {
+        Coupon {
+            code: "WELCOME".to_owned(),
+        }
+    }
+
+    #[derive(Serialize, JsonSchema)]
+    #[schemars(example = "example_coupon")]
+    struct Coupon {
+        code: String,
+    }
+
+    #[test]
+    fn test_into_json_schema_bundle() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.json_schema::<Account>();
+        gen.json_schema::<Coupon>();
+        let bundle = gen.into_json_schema_bundle();
+        assert_eq!(
+            bundle["$schema"],
+            "https://json-schema.org/draft/2020-12/schema"
+        );
+        let defs = bundle["$defs"].as_object().unwrap();
+        assert_eq!(
+            defs.keys().collect::<Vec<_>>(),
+            vec!["Account", "Coupon", "InnerA"]
+        );
+        assert_eq!(
+            defs["Coupon"]["examples"],
+            serde_json::json!([{"code": "WELCOME"}])
+        );
+        assert!(defs["Coupon"].get("example").is_none());
+        assert_eq!(
+            defs["Account"]["properties"]["group"],
+            serde_json::json!({"$ref": "#/$defs/InnerA"})
+        );
+    }
+
+    #[test]
+    fn test_write_to_file() {
+        let dir = std::env::temp_dir().join(format!("rocket_okapi_{}", std::process::id()));
+        let path = dir.join("spec").join("openapi.json");
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/users", Method::Get, "get_users"));
+        gen.write_to_file(&path, true).unwrap();
+        let json = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(json.contains('\n'));
+        let spec: OpenApi = serde_json::from_str(&json).unwrap();
+        assert!(spec.paths.contains_key("/users"));
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct InternalStats {
+        shared: InnerA,
+        nested: InnerB,
+    }
+
+    #[test]
+    fn test_retain_operations() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let internal = gen.json_schema::<InternalStats>();
+        let shared = gen.json_schema::<FooResponse>();
+        gen.add_operation(schema_operation_info("/internal/stats", internal));
+        gen.add_operation(schema_operation_info("/foo", shared));
+        let mut tagged = operation_info("/tagged", Method::Get, "tagged");
+        tagged.operation.tags.push("internal".to_owned());
+        gen.add_operation(tagged);
+        gen.retain_operations(|path, _, op| {
+            !path.starts_with("/internal/") && !op.tags.iter().any(|tag| tag == "internal")
+        });
+        let spec = gen.into_openapi();
+        assert_eq!(spec.paths.keys().collect::<Vec<_>>(), vec!["/foo"]);
+        let schemas = spec.components.unwrap().schemas;
+        let mut names: Vec<_> = schemas.keys().collect();
+        names.sort();
+        assert_eq!(names, vec!["FooResponse", "InnerA"]);
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Ping {
+        pong: Box<Pong>,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Pong {
+        ping: Option<Box<Ping>>,
+    }
+
+    #[test]
+    fn test_prune_unused_schemas() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let tree = gen.json_schema::<TreeNode>();
+        let ping = gen.json_schema::<Ping>();
+        gen.json_schema::<InnerA>();
+        gen.add_operation(schema_operation_info("/tree", tree));
+        gen.add_operation(schema_operation_info("/ping", ping));
+        gen.prune_unused_schemas();
+        let mut names: Vec<_> = gen.definitions().keys().cloned().collect();
+        names.sort();
+        assert_eq!(names, vec!["Ping", "Pong", "TreeNode"]);
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Labels(std::collections::HashMap<String, serde_json::Value>);
+
+    #[test]
+    fn test_set_additional_properties() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.json_schema::<Labels>();
+        let missing = gen.set_additional_properties("Missing", AdditionalProperties::Bool(false));
+        assert!(missing.is_err());
+        let value = gen.json_schema::<InnerA>();
+        gen.set_additional_properties(
+            "Labels",
+            AdditionalProperties::Schema(Box::new(value.into())),
+        )
+        .unwrap();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(
+            spec["components"]["schemas"]["Labels"],
+            serde_json::json!({
+                "type": "object",
+                "additionalProperties": {"$ref": "#/components/schemas/InnerA"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_operations() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/user", Method::Get, "a"));
+        gen.add_operation(operation_info("/user", Method::Post, "b"));
+        gen.add_operation(operation_info("/pet", Method::Delete, "c"));
+        let mut operations: Vec<_> = gen
+            .operations()
+            .map(|(path, method, op)| (path.to_owned(), method, op.summary.clone().unwrap()))
+            .collect();
+        operations.sort_by(|a, b| a.2.cmp(&b.2));
+        assert_eq!(
+            operations,
+            vec![
+                ("/user".to_owned(), Method::Get, "a".to_owned()),
+                ("/user".to_owned(), Method::Post, "b".to_owned()),
+                ("/pet".to_owned(), Method::Delete, "c".to_owned()),
+            ]
+        );
+        assert_eq!(gen.into_openapi().paths.len(), 2);
+    }
+
+    #[test]
+    fn test_replace_and_remove_operation() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/user", Method::Get, "generated"));
+        let old = gen.replace_operation(operation_info("/user", Method::Get, "hand written"));
+        assert_eq!(old.unwrap().summary.as_deref(), Some("generated"));
+        assert!(gen
+            .replace_operation(operation_info("/user", Method::Post, "new"))
+            .is_none());
+        let removed = gen.remove_operation("/user", Method::Post).unwrap();
+        assert_eq!(removed.summary.as_deref(), Some("new"));
+        assert!(gen.remove_operation("/user", Method::Post).is_none());
+        assert!(gen.remove_operation("/pet", Method::Get).is_none());
+        let spec = gen.into_openapi();
+        let get = spec.paths["/user"].get.as_ref().unwrap();
+        assert_eq!(get.summary.as_deref(), Some("hand written"));
+        assert!(spec.paths["/user"].post.is_none());
+    }
+
+    #[test]
+    fn test_base_path() {
+        let settings = OpenApiSettings {
+            base_path: Some("/api/v2/".to_owned()),
+            ..OpenApiSettings::default()
+        };
+        let mut gen = OpenApiGenerator::new(&settings);
+        gen.add_operation(operation_info("/users", Method::Get, "a"));
+        gen.add_operation(operation_info("/users/{id}", Method::Get, "b"));
+        let spec = gen.into_openapi();
+        let mut paths: Vec<_> = spec.paths.keys().cloned().collect();
+        paths.sort();
+        assert_eq!(paths, vec!["/api/v2/users", "/api/v2/users/{id}"]);
+
+        let keep = TrailingSlash::Keep;
+        assert_eq!(prefix_path("api", "/", keep), "/api");
+        assert_eq!(prefix_path("/", "/users", keep), "/users");
+        assert_eq!(prefix_path("//api//", "//users", keep), "/api/users");
+        assert_eq!(prefix_path("/api//v2", "/users/", keep), "/api/v2/users/");
+        assert_eq!(
+            prefix_path("/api", "/users/", TrailingSlash::Strip),
+            "/api/users"
+        );
+    }
+
+    #[test]
+    fn test_base_path_collision() {
+        let settings = OpenApiSettings {
+            base_path: Some("/api/".to_owned()),
+            ..OpenApiSettings::default()
+        };
+        let mut gen = OpenApiGenerator::new(&settings);
+        gen.add_operation(operation_info("/users", Method::Get, "first"));
+        gen.add_operation(operation_info("users", Method::Get, "second"));
+        gen.add_operation(operation_info("users", Method::Post, "create"));
+        let (spec, warnings) = gen.into_openapi_with_warnings();
+        assert_eq!(spec.paths.keys().collect::<Vec<_>>(), vec!["/api/users"]);
+        let path_item = &spec.paths["/api/users"];
+        assert_eq!(
+            path_item.get.as_ref().unwrap().summary.as_deref(),
+            Some("first")
+        );
+        assert!(path_item.post.is_some());
+        assert!(warnings.iter().any(|warning| matches!(
+            warning,
+            OpenApiWarning::OperationError(OpenApiError::DuplicateOperation {
+                path,
+                method: Method::Get,
+            }) if path == "/api/users"
+        )));
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Account {
+        id: u32,
+        user_name: String,
+        login: String,
+        group: InnerA,
+    }
+
+    #[test]
+    fn test_deprecate_property() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.json_schema::<Account>();
+        gen.deprecate_property("Account", "login").unwrap();
+        gen.deprecate_property("Account", "group").unwrap();
+        assert!(gen.deprecate_property("Account", "missing").is_err());
+        assert!(gen.deprecate_property("Missing", "login").is_err());
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let properties = &spec["components"]["schemas"]["Account"]["properties"];
+        assert_eq!(
+            properties["login"],
+            serde_json::json!({"type": "string", "deprecated": true})
+        );
+        assert_eq!(
+            properties["group"],
+            serde_json::json!({
+                "allOf": [{"$ref": "#/components/schemas/InnerA"}],
+                "deprecated": true
+            })
+        );
+        assert!(properties["id"].get("deprecated").is_none());
+        assert!(properties["user_name"].get("deprecated").is_none());
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct NewUser {
+        id: u32,
+        name: String,
+        password: String,
+    }
+
+    #[test]
+    fn test_set_property_const() {
+        for version in [OpenApiVersion::V3_0, OpenApiVersion::V3_1].iter().copied() {
+            let mut gen = OpenApiGenerator::new(&OpenApiSettings::default().with_version(version));
+            gen.json_schema::<NewUser>();
+            gen.set_property_const("NewUser", "name", serde_json::json!("user"))
+                .unwrap();
+            assert!(gen
+                .set_property_const("NewUser", "missing", serde_json::json!("user"))
+                .is_err());
+            assert!(gen
+                .set_property_const("Missing", "name", serde_json::json!("user"))
+                .is_err());
+            let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+            let name = &spec["components"]["schemas"]["NewUser"]["properties"]["name"];
+            if version == OpenApiVersion::V3_1 {
+                assert_eq!(
+                    name,
+                    &serde_json::json!({"type": "string", "const": "user"})
+                );
+            } else {
+                assert_eq!(
+                    name,
+                    &serde_json::json!({"type": "string", "enum": ["user"]})
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_set_property_access() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.json_schema::<NewUser>();
+        gen.json_schema::<Account>();
+        gen.set_property_access("NewUser", "id", PropertyAccess::ReadOnly)
+            .unwrap();
+        gen.set_property_access("NewUser", "password", PropertyAccess::WriteOnly)
+            .unwrap();
+        gen.set_property_access("NewUser", "name", PropertyAccess::ReadWrite)
+            .unwrap();
+        gen.set_property_access("Account", "group", PropertyAccess::ReadOnly)
+            .unwrap();
+        assert!(gen
+            .set_property_access("NewUser", "missing", PropertyAccess::ReadOnly)
+            .is_err());
+        assert!(gen
+            .set_property_access("Missing", "id", PropertyAccess::ReadOnly)
+            .is_err());
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let properties = &spec["components"]["schemas"]["NewUser"]["properties"];
+        assert_eq!(properties["id"]["readOnly"], true);
+        assert!(properties["id"].get("writeOnly").is_none());
+        assert_eq!(properties["password"]["writeOnly"], true);
+        assert!(properties["password"].get("readOnly").is_none());
+        assert_eq!(properties["name"], serde_json::json!({"type": "string"}));
+        assert_eq!(
+            spec["components"]["schemas"]["Account"]["properties"]["group"],
+            serde_json::json!({
+                "allOf": [{"$ref": "#/components/schemas/InnerA"}],
+                "readOnly": true
+            })
+        );
+    }
+
+    #[test]
+    fn test_set_property_constraints() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.json_schema::<Account>();
+        gen.set_property_constraints(
+            "Account",
+            "login",
+            Constraints {
+                pattern: Some("^[a-z]+$".to_owned()),
+                max_length: Some(32),
+                ..Constraints::default()
+            },
+        )
+        .unwrap();
+        gen.set_property_constraints(
+            "Account",
+            "id",
+            Constraints {
+                maximum: Some(1000.0),
+                ..Constraints::default()
+            },
+        )
+        .unwrap();
+        assert!(gen.take_warnings().is_empty());
+        gen.set_property_constraints(
+            "Account",
+            "user_name",
+            Constraints {
+                minimum: Some(1.0),
+                ..Constraints::default()
+            },
+        )
+        .unwrap();
+        assert!(matches!(
+            gen.take_warnings().as_slice(),
+            [OpenApiWarning::InvalidConstraint { property, .. }] if property == "user_name"
+        ));
+        assert!(gen
+            .set_property_constraints("Account", "missing", Constraints::default())
+            .is_err());
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let properties = &spec["components"]["schemas"]["Account"]["properties"];
+        assert_eq!(
+            properties["login"],
+            serde_json::json!({"type": "string", "maxLength": 32, "pattern": "^[a-z]+$"})
+        );
+        assert_eq!(properties["id"]["maximum"], 1000.0);
+        assert_eq!(properties["id"]["minimum"], 0.0);
+    }
+
+    #[test]
+    fn test_schema_generator_mut() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let mut money = SchemaObject::default();
+        money.metadata().description = Some("An amount of money, like `12.50`.".to_owned());
+        gen.schema_generator_mut()
+            .definitions_mut()
+            .insert("Money".to_owned(), money.clone().into());
+        let spec = gen.into_openapi();
+        let schemas = spec.components.unwrap().schemas;
+        assert_eq!(schemas.get("Money"), Some(&money));
+    }
+
+    #[test]
+    fn test_add_json_response() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/upload", Method::Post, "a"));
+        gen.add_json_response::<InnerA>("/upload", Method::Post, 201, "The upload is done.")
+            .unwrap();
+        gen.add_json_response::<InnerB>("/upload", Method::Post, 202, "The upload is queued.")
+            .unwrap();
+        assert!(gen
+            .add_json_response::<InnerB>("/missing", Method::Post, 202, "")
+            .is_err());
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let responses = &spec["paths"]["/upload"]["post"]["responses"];
+        assert_eq!(responses["201"]["description"], "The upload is done.");
+        assert_eq!(
+            responses["201"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/InnerA"
+        );
+        assert_eq!(responses["202"]["description"], "The upload is queued.");
+        assert_eq!(
+            responses["202"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/InnerB"
+        );
+    }
+
+    #[derive(JsonSchema)]
+    #[serde(rename_all = "camelCase")]
+    #[allow(dead_code)]
+    struct CamelAccount {
+        user_name: String,
+        group: InnerA,
+    }
+
+    #[test]
+    fn test_check_serde_casing() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.json_schema::<CamelAccount>();
+        assert!(gen.check_serde_casing(PropertyCasing::CamelCase).is_empty());
+        gen.json_schema::<Account>();
+        assert_eq!(
+            gen.check_serde_casing(PropertyCasing::CamelCase),
+            vec!["The property `user_name` of the schema `Account` does not use CamelCase."]
+        );
+        assert!(PropertyCasing::SnakeCase.matches("user_name"));
+        assert!(!PropertyCasing::SnakeCase.matches("userName"));
+        assert!(PropertyCasing::KebabCase.matches("user-name"));
+        assert!(PropertyCasing::PascalCase.matches("UserName"));
+        assert!(PropertyCasing::ScreamingSnakeCase.matches("USER_NAME"));
+    }
+
+    #[test]
+    fn test_binary_content() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/files", Method::Post, "a"));
+        gen.add_binary_request_body("/files", Method::Post, "application/octet-stream", true)
+            .unwrap();
+        gen.add_binary_response("/files", Method::Post, 200, "image/png")
+            .unwrap();
+        assert!(gen
+            .add_binary_response("/missing", Method::Get, 200, "image/png")
+            .is_err());
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let operation = &spec["paths"]["/files"]["post"];
+        let binary = serde_json::json!({"type": "string", "format": "binary"});
+        assert_eq!(operation["requestBody"]["required"], true);
+        assert_eq!(
+            operation["requestBody"]["content"]["application/octet-stream"]["schema"],
+            binary
+        );
+        assert_eq!(
+            operation["responses"]["200"]["content"]["image/png"]["schema"],
+            binary
+        );
+    }
+
+    #[test]
+    fn test_add_multipart_body() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/upload", Method::Post, "a"));
+        let parts = vec![
+            MultipartPart::new("metadata", gen.json_schema::<InnerA>())
+                .with_content_type("application/json"),
+            MultipartPart::binary("file")
+                .with_content_type("image/png, image/jpeg")
+                .with_header("X-Rate-Limit", gen.header_for::<u32>(None, false)),
+            MultipartPart::new("comment", gen.json_schema::<String>()).with_required(false),
+        ];
+        gen.add_multipart_body("/upload", Method::Post, parts)
+            .unwrap();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let body = &spec["paths"]["/upload"]["post"]["requestBody"];
+        assert_eq!(body["required"], true);
+        let media = &body["content"]["multipart/form-data"];
+        assert_eq!(media["schema"]["type"], "object");
+        assert_eq!(
+            media["schema"]["properties"]["metadata"]["$ref"],
+            "#/components/schemas/InnerA"
+        );
+        assert_eq!(
+            media["schema"]["properties"]["file"],
+            serde_json::json!({"type": "string", "format": "binary"})
+        );
+        let mut required: Vec<_> = media["schema"]["required"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|name| name.as_str().unwrap())
+            .collect();
+        required.sort_unstable();
+        assert_eq!(required, vec!["file", "metadata"]);
+        assert_eq!(
+            media["encoding"]["metadata"],
+            serde_json::json!({"contentType": "application/json"})
+        );
+        assert_eq!(
+            media["encoding"]["file"]["contentType"],
+            "image/png, image/jpeg"
+        );
+        assert!(media["encoding"]["file"]["headers"]["X-Rate-Limit"].is_object());
+        assert!(media["encoding"].get("comment").is_none());
+    }
+
+    #[test]
+    fn test_with_schema_settings() {
+        let settings = OpenApiSettings::new().with_schema_settings(|settings| {
+            settings.option_nullable = false;
+        });
+        let mut gen = OpenApiGenerator::new(&settings);
+        gen.json_schema::<NullableInner>();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let name = &spec["components"]["schemas"]["NullableInner"]["properties"]["name"];
+        assert_eq!(name, &serde_json::json!({"type": "string"}));
+
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.json_schema::<NullableInner>();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let name = &spec["components"]["schemas"]["NullableInner"]["properties"]["name"];
+        assert_eq!(name["nullable"], true);
+    }
+
+    #[test]
+    fn test_check_documentation() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/user", Method::Get, "Get a user"));
+        gen.add_operation(operation_info("/user", Method::Post, " "));
+        gen.add_operation(OperationInfo {
+            path: "/pet".to_owned(),
+            method: Method::Get,
+            operation: Operation::default(),
+        });
+        assert_eq!(
+            gen.check_documentation(),
+            vec![
+                ("/pet".to_owned(), Method::Get),
+                ("/user".to_owned(), Method::Post)
+            ]
+        );
+        let errors = gen.into_openapi_strict().unwrap_err();
+        let missing: Vec<_> = errors
+            .iter()
+            .filter(|error| matches!(error, OpenApiError::MissingSummary { .. }))
+            .collect();
+        assert_eq!(missing.len(), 2);
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct ApiError {
+        code: u16,
+        message: String,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Problem {
+        #[serde(rename = "type")]
+        kind: String,
+        title: String,
+        detail: Option<String>,
+    }
+
+    #[test]
+    fn test_add_problem_json_response() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/user", Method::Post, "a"));
+        gen.add_json_response::<ApiError>("/user", Method::Post, 400, "Bad request.")
+            .unwrap();
+        gen.add_problem_json_response::<Problem>("/user", Method::Post, 400)
+            .unwrap();
+        assert!(gen
+            .add_problem_json_response::<Problem>("/missing", Method::Post, 400)
+            .is_err());
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let content = &spec["paths"]["/user"]["post"]["responses"]["400"]["content"];
+        assert_eq!(
+            content["application/json"]["schema"]["$ref"],
+            "#/components/schemas/ApiError"
+        );
+        assert_eq!(
+            content["application/problem+json"]["schema"]["$ref"],
+            "#/components/schemas/Problem"
+        );
+    }
+
+    fn server_with_variable(enumeration: Option<Vec<&str>>, default: &str) -> Server {
+        let mut server = Server {
+            url: "https://{environment}.example.com".to_owned(),
+            ..Server::default()
+        };
+        server.variables.insert(
+            "environment".to_owned(),
+            okapi::openapi3::ServerVariable {
+                enumeration: enumeration
+                    .map(|values| values.into_iter().map(str::to_owned).collect()),
+                default: default.to_owned(),
+                ..okapi::openapi3::ServerVariable::default()
+            },
+        );
+        server
+    }
+
+    #[test]
+    fn test_try_add_server() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let bad_default = server_with_variable(Some(vec!["api", "staging"]), "dev");
+        assert!(gen.try_add_server(bad_default).is_err());
+        let empty_enum = server_with_variable(Some(vec![]), "dev");
+        assert!(gen.try_add_server(empty_enum).is_err());
+        gen.try_add_server(server_with_variable(Some(vec!["api", "staging"]), "api"))
+            .unwrap();
+        gen.try_add_server(server_with_variable(None, "dev"))
+            .unwrap();
+        assert_eq!(gen.into_openapi().servers.len(), 2);
+    }
+
+    #[test]
+    fn test_add_server_warning() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_server(server_with_variable(Some(vec!["api"]), "dev"));
+        assert!(matches!(gen.warnings(), [OpenApiWarning::InvalidServer(_)]));
+        assert_eq!(gen.into_openapi().servers.len(), 1);
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Described {
+        /// The first part.
+        first: InnerA,
+        second: InnerB,
+    }
+
+    fn described_schema(version: OpenApiVersion) -> serde_json::Value {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::new().with_version(version));
+        gen.json_schema::<Described>();
+        let definitions = gen.schema_generator_mut().definitions_mut();
+        if let Some(Schema::Object(described)) = definitions.get_mut("Described") {
+            let second = SchemaObject {
+                reference: Some("#/components/schemas/InnerB".to_owned()),
+                metadata: Some(Box::new(schemars::schema::Metadata {
+                    description: Some("The second part.".to_owned()),
+                    ..Default::default()
+                })),
+                ..SchemaObject::default()
+            };
+            described
+                .object()
+                .properties
+                .insert("second".to_owned(), second.into());
+        }
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        spec["components"]["schemas"]["Described"]["properties"].clone()
+    }
+
+    #[test]
+    fn test_ref_with_siblings_3_0() {
+        let properties = described_schema(OpenApiVersion::V3_0);
+        assert_eq!(
+            properties["first"],
+            serde_json::json!({
+                "allOf": [{"$ref": "#/components/schemas/InnerA"}],
+                "description": "The first part."
+            })
+        );
+        assert_eq!(
+            properties["second"],
+            serde_json::json!({
+                "allOf": [{"$ref": "#/components/schemas/InnerB"}],
+                "description": "The second part."
+            })
+        );
+    }
+
+    #[test]
+    fn test_ref_with_siblings_3_1() {
+        let properties = described_schema(OpenApiVersion::V3_1);
+        assert_eq!(
+            properties["first"],
+            serde_json::json!({
+                "$ref": "#/components/schemas/InnerA",
+                "description": "The first part."
+            })
+        );
+        assert_eq!(
+            properties["second"],
+            serde_json::json!({
+                "$ref": "#/components/schemas/InnerB",
+                "description": "The second part."
+            })
+        );
+    }
+
+    fn collision_gen(on_collision: CollisionPolicy) -> OpenApiGenerator {
+        let settings = OpenApiSettings {
+            on_collision,
+            ..OpenApiSettings::default()
+        };
+        let mut gen = OpenApiGenerator::new(&settings);
+        gen.add_operation(operation_info("/user", Method::Get, "first"));
+        gen.add_operation(operation_info("/user", Method::Get, "last"));
+        gen
+    }
+
+    fn user_summary(gen: OpenApiGenerator) -> Option<String> {
+        gen.into_openapi().paths["/user"]
+            .get
+            .as_ref()?
+            .summary
+            .clone()
+    }
+
+    #[test]
+    fn test_collision_policy() {
+        let gen = collision_gen(CollisionPolicy::KeepFirst);
+        assert!(gen.warnings().is_empty());
+        assert_eq!(user_summary(gen).as_deref(), Some("first"));
+
+        let gen = collision_gen(CollisionPolicy::KeepLast);
+        assert!(gen.warnings().is_empty());
+        assert_eq!(user_summary(gen).as_deref(), Some("last"));
+
+        let gen = collision_gen(CollisionPolicy::Error);
+        assert!(matches!(
+            gen.warnings(),
+            [OpenApiWarning::OperationError(
+                OpenApiError::DuplicateOperation { .. }
+            )]
+        ));
+        assert_eq!(user_summary(gen).as_deref(), Some("first"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_collision_policy_panic() {
+        collision_gen(CollisionPolicy::Panic);
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    #[serde(rename_all = "lowercase")]
+    enum Size {
+        Small,
+        Large,
+    }
+
+    #[test]
+    fn test_set_enum_varnames() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.json_schema::<Size>();
+        let names = |names: &[&str]| names.iter().map(|name| (*name).to_owned()).collect();
+        assert!(gen.set_enum_varnames("Size", names(&["Small"])).is_err());
+        assert!(gen.set_enum_varnames("InnerA", names(&[])).is_err());
+        gen.json_schema::<InnerA>();
+        assert!(gen.set_enum_varnames("InnerA", names(&[])).is_err());
+        gen.set_enum_varnames("Size", names(&["Small", "Large"]))
+            .unwrap();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let size = &spec["components"]["schemas"]["Size"];
+        assert_eq!(size["enum"], serde_json::json!(["small", "large"]));
+        assert_eq!(
+            size["x-enum-varnames"],
+            serde_json::json!(["Small", "Large"])
+        );
+    }
+
+    #[test]
+    fn test_set_media_type_encoding() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/export", Method::Get, "a"));
+        gen.add_json_response::<InnerA>("/export", Method::Get, 200, "The export.")
+            .unwrap();
+        let mut encoding = Map::new();
+        encoding.insert(
+            "data".to_owned(),
+            Encoding {
+                content_type: Some("application/gzip".to_owned()),
+                ..Encoding::default()
+            },
+        );
+        gen.set_media_type_encoding("/export", Method::Get, 200, "application/json", encoding)
+            .unwrap();
+        assert!(gen
+            .set_media_type_encoding("/missing", Method::Get, 200, "application/json", Map::new())
+            .is_err());
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let media =
+            &spec["paths"]["/export"]["get"]["responses"]["200"]["content"]["application/json"];
+        assert_eq!(
+            media["encoding"],
+            serde_json::json!({"data": {"contentType": "application/gzip"}})
+        );
+        assert_eq!(media["schema"]["$ref"], "#/components/schemas/InnerA");
+    }
+
+    #[test]
+    fn test_definitions() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.json_schema::<InnerA>();
+        assert_eq!(gen.definitions().keys().collect::<Vec<_>>(), vec!["InnerA"]);
+        gen.json_schema::<InnerB>();
+        assert_eq!(gen.definitions().len(), 2);
+        let schemas = gen.into_openapi().components.unwrap().schemas;
+        assert!(schemas.contains_key("InnerA") && schemas.contains_key("InnerB"));
+    }
+
+    struct NoAdditionalProperties;
+
+    impl crate::visit::SchemaVisitor for NoAdditionalProperties {
+        fn visit(&self, schema: &mut SchemaObject) {
+            if let Some(object) = &mut schema.object {
+                object.additional_properties = Some(Box::new(Schema::Bool(false)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_schema_visitors() {
+        let settings = OpenApiSettings::default().with_schema_visitor(NoAdditionalProperties);
+        let mut gen = OpenApiGenerator::new(&settings);
+        gen.json_schema::<Parent>();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let schemas = spec["components"]["schemas"].as_object().unwrap();
+        assert!(schemas.len() > 1);
+        for schema in schemas.values() {
+            assert_eq!(schema["additionalProperties"], serde_json::json!(false));
+        }
+    }
+
+    #[derive(JsonSchema)]
+    #[schemars(tag = "kind")]
+    #[allow(dead_code)]
+    enum Shape {
+        Circle { radius: f64 },
+        Square { side: f64 },
+    }
+
+    #[test]
+    fn test_set_discriminator() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.json_schema::<Shape>();
+        assert!(gen.set_discriminator("Shape", "side", Map::new()).is_err());
+        assert!(gen
+            .set_discriminator("Missing", "kind", Map::new())
+            .is_err());
+        let mut mapping = Map::new();
+        mapping.insert(
+            "Circle".to_owned(),
+            "#/components/schemas/Circle".to_owned(),
+        );
+        gen.set_discriminator("Shape", "kind", mapping).unwrap();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(
+            spec["components"]["schemas"]["Shape"]["discriminator"],
+            serde_json::json!({
+                "propertyName": "kind",
+                "mapping": {"Circle": "#/components/schemas/Circle"}
+            })
+        );
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Nullable {
+        name: Option<String>,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct NullableInner {
+        inner: Option<InnerA>,
+        name: Option<String>,
+    }
+
+    #[test]
+    fn test_null_type_to_nullable() {
+        // Schemas that use a `null` type, like the JSON Schema settings create.
+        let settings = OpenApiSettings {
+            schema_settings: OpenApiVersion::V3_0.schema_settings().with(|s| {
+                s.option_nullable = false;
+                s.option_add_null_type = true;
+            }),
+            ..OpenApiSettings::default()
+        };
+        let mut gen = OpenApiGenerator::new(&settings);
+        gen.json_schema::<NullableInner>();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let properties = &spec["components"]["schemas"]["NullableInner"]["properties"];
+        assert_eq!(
+            properties["inner"],
+            serde_json::json!({
+                "allOf": [{"$ref": "#/components/schemas/InnerA"}],
+                "nullable": true
+            })
+        );
+        assert_eq!(
+            properties["name"],
+            serde_json::json!({"type": "string", "nullable": true})
+        );
+    }
+
+    fn nullable_property_json(settings: &OpenApiSettings) -> serde_json::Value {
+        let mut gen = OpenApiGenerator::new(settings);
+        gen.json_schema::<Nullable>();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        spec["components"]["schemas"]["Nullable"]["properties"]["name"].clone()
+    }
+
+    #[test]
+    fn test_nullable_per_version() {
+        let settings = OpenApiSettings::default();
+        assert_eq!(
+            nullable_property_json(&settings),
+            serde_json::json!({"type": "string", "nullable": true})
+        );
+        let settings = OpenApiSettings::default().with_version(OpenApiVersion::V3_1);
+        assert_eq!(
+            nullable_property_json(&settings),
+            serde_json::json!({"type": ["string", "null"]})
+        );
+        // Schemas generated with the OpenAPI 3.0 settings are converted as well.
+        let settings = OpenApiSettings {
+            version: OpenApiVersion::V3_1,
+            ..OpenApiSettings::default()
+        };
+        assert_eq!(
+            nullable_property_json(&settings),
+            serde_json::json!({"type": ["string", "null"]})
+        );
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct InnerA {
+        value: u32,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct InnerB {
+        value: u32,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct FooResponse {
+        inner: InnerA,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct BarResponse {
+        inner: InnerB,
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct Parent {
+        child: Option<InnerA>,
+    }
+
+    #[test]
+    fn test_definitions_path() {
+        let settings = OpenApiSettings::default().with_definitions_path("#/definitions/");
+        let mut gen = OpenApiGenerator::new(&settings);
+        let schema = gen.json_schema::<Parent>();
+        gen.add_operation(schema_operation_info("/parent", schema));
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(
+            spec["paths"]["/parent"]["get"]["responses"]["200"]["content"]["application/json"]
+                ["schema"]["$ref"],
+            "#/definitions/Parent"
+        );
+        let schemas = &spec["components"]["schemas"];
+        let child = &schemas["Parent"]["properties"]["child"];
+        assert_eq!(child["allOf"][0]["$ref"], "#/definitions/InnerA");
+        assert!(schemas["InnerA"].is_object());
+    }
+
+    #[derive(JsonSchema, Serialize)]
+    struct Example {
+        value: u32,
+    }
+
+    #[test]
+    fn test_add_response_example() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let schema = gen.json_schema::<Example>();
+        gen.add_operation(schema_operation_info("/example", schema));
+        gen.add_response_example("/example", Method::Get, 200, &Example { value: 1 })
+            .unwrap();
+        assert!(gen.warnings().is_empty());
+        gen.add_response_example("/example", Method::Get, 200, &"text")
+            .unwrap();
+        assert_eq!(gen.warnings().len(), 1);
+        assert!(gen
+            .add_response_example("/example", Method::Get, 404, &"text")
+            .is_err());
+        gen.add_response_example("/example", Method::Get, 200, &Example { value: 2 })
+            .unwrap();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        assert_eq!(
+            spec["paths"]["/example"]["get"]["responses"]["200"]["content"]["application/json"]
+                ["example"],
+            serde_json::json!({"value": 2})
+        );
+    }
+
+    #[test]
+    fn test_add_request_content() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        gen.add_operation(operation_info("/example", Method::Post, "a"));
+        let json = gen.json_schema::<Example>();
+        let form = gen.json_schema::<Example>();
+        gen.add_request_content("/example", Method::Post, "application/json", json)
+            .unwrap();
+        gen.add_request_content(
+            "/example",
+            Method::Post,
+            "application/x-www-form-urlencoded",
+            form,
+        )
+        .unwrap();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let content = spec["paths"]["/example"]["post"]["requestBody"]["content"]
+            .as_object()
+            .unwrap();
+        assert_eq!(
+            content.keys().collect::<Vec<_>>(),
+            vec!["application/json", "application/x-www-form-urlencoded"]
+        );
+        for media_type in content.values() {
+            assert_eq!(media_type["schema"]["$ref"], "#/components/schemas/Example");
+        }
+    }
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct TreeNode {
+        children: Vec<TreeNode>,
+    }
+
+    #[test]
+    fn test_inline_operation_schemas() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let foo = gen.json_schema::<FooResponse>();
+        let tree = gen.json_schema::<TreeNode>();
+        gen.add_operation(schema_operation_info("/foo", foo));
+        gen.add_operation(schema_operation_info("/tree", tree));
+        let response_schema = |path: &str| {
+            let operation = gen.inline_operation_schemas(path, Method::Get).unwrap();
+            let operation = serde_json::to_value(operation).unwrap();
+            operation["responses"]["200"]["content"]["application/json"]["schema"].clone()
+        };
+
+        let foo = response_schema("/foo");
+        assert!(!foo.to_string().contains("$ref"));
+        assert_eq!(
+            foo["properties"]["inner"]["properties"]["value"]["type"],
+            "integer"
+        );
+
+        let tree = response_schema("/tree");
+        assert_eq!(tree["properties"]["children"]["type"], "array");
+        assert_eq!(
+            tree["properties"]["children"]["items"]["$ref"],
+            "#/components/schemas/TreeNode"
+        );
+
+        assert!(matches!(
+            gen.inline_operation_schemas("/missing", Method::Get),
+            Err(OpenApiError::MissingOperation { .. })
+        ));
+    }
+
+    fn schema_operation_info(path: &str, schema: SchemaObject) -> OperationInfo {
+        let mut responses = okapi::openapi3::Responses::default();
+        crate::util::add_schema_response(&mut responses, 200, "application/json", schema).unwrap();
+        OperationInfo {
+            path: path.to_owned(),
+            method: Method::Get,
+            operation: Operation {
+                responses,
+                ..Operation::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_dedup_schemas() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let foo = gen.json_schema::<FooResponse>();
+        let bar = gen.json_schema::<BarResponse>();
+        gen.add_operation(schema_operation_info("/foo", foo));
+        gen.add_operation(schema_operation_info("/bar", bar));
+        gen.dedup_schemas();
+        let spec = serde_json::to_value(gen.into_openapi()).unwrap();
+        let schemas = spec["components"]["schemas"].as_object().unwrap();
+        let mut names = schemas.keys().collect::<Vec<_>>();
+        names.sort();
+        assert_eq!(names, vec!["FooResponse", "InnerA"]);
+        let schema_ref = |path: &str| {
+            spec["paths"][path]["get"]["responses"]["200"]["content"]["application/json"]["schema"]
+                ["$ref"]
+                .clone()
+        };
+        assert_eq!(schema_ref("/foo"), "#/components/schemas/FooResponse");
+        assert_eq!(schema_ref("/bar"), "#/components/schemas/FooResponse");
+    }
+
+    #[test]
+    fn test_dedup_schemas_component_response() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let foo = gen.json_schema::<FooResponse>();
+        let bar = gen.json_schema::<BarResponse>();
+        gen.add_operation(schema_operation_info("/foo", foo));
+        let mut response = Response::default();
+        response.content.insert(
+            "application/json".to_owned(),
+            MediaType {
+                schema: Some(bar),
+                ..MediaType::default()
+            },
+        );
+        gen.add_component_response("Bar", response);
+        gen.dedup_schemas();
+        let (spec, warnings) = gen.into_openapi_with_warnings();
+        assert!(!warnings
+            .iter()
+            .any(|warning| matches!(warning, OpenApiWarning::UnresolvedRef { .. })));
+        let spec = serde_json::to_value(spec).unwrap();
+        assert_eq!(
+            spec["components"]["responses"]["Bar"]["content"]["application/json"]["schema"],
+            serde_json::json!({"$ref": "#/components/schemas/FooResponse"})
+        );
+    }
 }
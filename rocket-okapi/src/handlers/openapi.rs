@@ -1,19 +1,60 @@
 use okapi::openapi3::{OpenApi, Server};
-use rocket::http::{ContentType, Method};
+use rocket::http::{ContentType, Method, Status};
+use rocket::response::{self, Responder};
 use rocket::route::{Handler, Outcome};
 use rocket::{Data, Request, Route};
 
+/// A `Responder` that serves an `OpenApi` document as JSON, for routes that return the document
+/// themselves. Use [`get_openapi_route`](crate::get_openapi_route) to serve it at the
+/// `OpenApiSettings::json_path` instead.
+///
+/// ```rust
+/// use rocket::get;
+/// use rocket_okapi::handlers::OpenApiJson;
+/// use rocket_okapi::okapi::openapi3::OpenApi;
+///
+/// #[get("/spec.json")]
+/// fn spec() -> OpenApiJson {
+///     OpenApiJson(OpenApi::new())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct OpenApiJson(pub OpenApi);
+
+impl<'r> Responder<'r, 'static> for OpenApiJson {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let json = self.0.to_json(true).map_err(|err| {
+            log::error!("Could not serialize the OpenApi document as JSON: {}", err);
+            Status::InternalServerError
+        })?;
+        (ContentType::JSON, json).respond_to(req)
+    }
+}
+
 /// A handler type that is used to serve the `openapi.json` files.
 #[derive(Clone)]
 pub struct OpenApiHandler {
     spec: OpenApi,
+    #[cfg(feature = "yaml")]
+    yaml: bool,
 }
 
 impl OpenApiHandler {
     /// Create a new handler from an API spec.
     #[must_use]
     pub fn new(spec: OpenApi) -> Self {
-        OpenApiHandler { spec }
+        OpenApiHandler {
+            spec,
+            #[cfg(feature = "yaml")]
+            yaml: false,
+        }
+    }
+
+    /// Create a new handler from an API spec that serves the spec as YAML.
+    #[cfg(feature = "yaml")]
+    #[must_use]
+    pub fn yaml(spec: OpenApi) -> Self {
+        OpenApiHandler { spec, yaml: true }
     }
 
     /// Create a new route from this `OpenApiHandler`.
@@ -39,8 +80,53 @@ impl Handler for OpenApiHandler {
             })
         }
 
-        let json =
-            serde_json::to_string_pretty(&spec).expect("Could not serialize content as JSON.");
-        Outcome::from(req, (ContentType::JSON, json))
+        #[cfg(feature = "yaml")]
+        if self.yaml {
+            return match spec.to_yaml() {
+                Ok(yaml) => Outcome::from(req, (ContentType::new("application", "yaml"), yaml)),
+                Err(err) => {
+                    log::error!("Could not serialize the OpenApi document as YAML: {}", err);
+                    Outcome::Failure(Status::InternalServerError)
+                }
+            };
+        }
+        Outcome::from(req, OpenApiJson(spec))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::settings::OpenApiSettings;
+    use rocket::local::blocking::Client;
+
+    fn spec() -> OpenApi {
+        let mut spec = OpenApi::new();
+        spec.info.title = "Pets".to_owned();
+        spec.info.version = "1.0.0".to_owned();
+        spec
+    }
+
+    fn custom<'r>(req: &'r Request<'_>, _: Data<'r>) -> rocket::route::BoxFuture<'r> {
+        Outcome::from(req, OpenApiJson(spec())).pin()
+    }
+
+    #[test]
+    fn test_serve_json() {
+        let settings = OpenApiSettings {
+            json_path: "/spec/openapi.json".to_owned(),
+            ..OpenApiSettings::default()
+        };
+        let rocket = rocket::build()
+            .mount("/", vec![crate::get_openapi_route(spec(), &settings)])
+            .mount("/", vec![Route::new(Method::Get, "/custom.json", custom)]);
+        let client = Client::tracked(rocket).unwrap();
+        for path in ["/spec/openapi.json", "/custom.json"].iter().copied() {
+            let response = client.get(path).dispatch();
+            assert_eq!(response.status(), Status::Ok);
+            assert_eq!(response.content_type(), Some(ContentType::JSON));
+            let body: OpenApi = serde_json::from_str(&response.into_string().unwrap()).unwrap();
+            assert_eq!(body.info.title, "Pets");
+        }
     }
 }
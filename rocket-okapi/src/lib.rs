@@ -72,7 +72,12 @@
 //!
 
 mod error;
+mod operation_info;
+mod security_scheme;
+mod warning;
 
+/// Contains the `OpenApiFairing`, which mounts routes together with their documentation.
+pub mod fairing;
 /// Contains the `Generator` struct, which you can use to manually control the way a struct is
 /// represented in the documentation.
 pub mod gen;
@@ -82,6 +87,9 @@ pub mod handlers;
 /// Contains the functions and structs required to display the RapiDoc UI.
 #[cfg(feature = "rapidoc")]
 pub mod rapidoc;
+/// Contains the functions and structs required to display the Redoc UI.
+#[cfg(feature = "redoc")]
+pub mod redoc;
 /// This module contains several traits that correspond to the `Rocket` traits pertaining to request
 /// guards and responses
 pub mod request;
@@ -96,12 +104,17 @@ pub mod settings;
 pub mod swagger_ui;
 /// Assorted function that are used throughout the application.
 pub mod util;
+/// Contains functions and `Visitor`s to modify all the schemas in an `OpenApi` document.
+pub mod visit;
 
 pub use error::*;
 /// Re-export Okapi
 pub use okapi;
+pub use operation_info::*;
 pub use rocket_okapi_codegen::*;
 pub use schemars::JsonSchema;
+pub use security_scheme::*;
+pub use warning::*;
 
 /// Contains information about an endpoint.
 pub struct OperationInfo {
@@ -123,6 +136,42 @@ pub fn get_openapi_route(
     handlers::OpenApiHandler::new(spec).into_route(&settings.json_path)
 }
 
+/// Convert OpenApi object to all routable endpoints.
+///
+/// Used to serve an `OpenApi` object as an `openapi.json` file in Rocket. When the `yaml`
+/// feature is enabled and `OpenApiSettings::yaml_path` is set, the `OpenApi` object is also
+/// served as a yaml file. When `OpenApiSettings::doc_renderer` is set, the documentation UI
+/// is served at `OpenApiSettings::docs_path`.
+pub fn get_openapi_routes(
+    spec: okapi::openapi3::OpenApi,
+    settings: &settings::OpenApiSettings,
+) -> Vec<rocket::Route> {
+    let mut routes = Vec::new();
+    #[cfg(feature = "yaml")]
+    if let Some(yaml_path) = &settings.yaml_path {
+        routes.push(handlers::OpenApiHandler::yaml(spec.clone()).into_route(yaml_path));
+    }
+    if let Some(doc_renderer) = &settings.doc_renderer {
+        // The url of the spec relative to the docs page.
+        let depth = settings.docs_path.trim_matches('/').matches('/').count();
+        let spec_url = format!(
+            "{}{}",
+            "../".repeat(depth),
+            settings.json_path.trim_start_matches('/')
+        );
+        let html = doc_renderer.html(&spec_url);
+        routes.push(
+            handlers::ContentHandler::bytes_owned(
+                rocket::http::ContentType::HTML,
+                html.into_bytes(),
+            )
+            .into_route(&settings.docs_path),
+        );
+    }
+    routes.push(get_openapi_route(spec, settings));
+    routes
+}
+
 /// Mount endpoints and mount merged OpenAPI documentation.
 ///
 /// This marco just makes to code look cleaner and improves readability
@@ -131,11 +180,11 @@ pub fn get_openapi_route(
 /// The macro expects the following arguments:
 /// - rocket_builder: `Rocket<Build>`,
 /// - base_path: `&str`, `String` or [`Uri`](rocket::http::uri::Uri). (Anything that implements `ToString`)
-/// Anything accepted by [`mount()`](https://docs.rs/rocket/0.5.0-rc.1/rocket/struct.Rocket.html#method.mount)
+///   Anything accepted by [`mount()`](https://docs.rs/rocket/0.5.0-rc.1/rocket/struct.Rocket.html#method.mount)
 /// - openapi_settings: `OpenApiSettings` (use `OpenApiSettings::default()` if default settings are okay for you),
 /// - List of (0 or more):
 ///   - path:  `&str`, `String` or [`Uri`](rocket::http::uri::Uri).
-///   Anything accepted by `mount()` (`base_path` should not be included).
+///     Anything accepted by `mount()` (`base_path` should not be included).
 ///   - `=>`: divider
 ///   - route_and_docs: `(Vec<rocket::Route>, OpenApi)`
 ///
@@ -170,10 +219,7 @@ macro_rules! mount_endpoints_and_merged_docs {
         // Add OpenApi route
         $rocket_builder = $rocket_builder.mount(
             $base_path,
-            vec![rocket_okapi::get_openapi_route(
-                openapi_docs,
-                &$openapi_settings,
-            )],
+            rocket_okapi::get_openapi_routes(openapi_docs, &$openapi_settings),
         );
     }};
 }
@@ -0,0 +1,189 @@
+use crate::gen::OpenApiGenerator;
+use crate::util::add_schema_response;
+use crate::OperationInfo;
+use okapi::openapi3::{Operation, Response};
+use rocket::http::Method;
+use schemars::JsonSchema;
+
+/// Builder to create an [`OperationInfo`] for operations that are added by hand using
+/// [`OpenApiGenerator::add_operation`].
+///
+/// ```rust
+/// use rocket::http::Method;
+/// use rocket_okapi::gen::OpenApiGenerator;
+/// use rocket_okapi::settings::OpenApiSettings;
+/// use rocket_okapi::OperationInfo;
+///
+/// let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+/// let info = OperationInfo::builder()
+///     .path("/users")
+///     .method(Method::Get)
+///     .summary("Get all users")
+///     .json_response::<Vec<String>>(&mut gen, 200)
+///     .build();
+/// gen.add_operation(info);
+/// ```
+#[derive(Debug, Clone)]
+pub struct OperationInfoBuilder {
+    path: String,
+    method: Method,
+    operation: Operation,
+}
+
+impl Default for OperationInfoBuilder {
+    fn default() -> Self {
+        OperationInfoBuilder {
+            path: "/".to_owned(),
+            method: Method::Get,
+            operation: Operation::default(),
+        }
+    }
+}
+
+impl OperationInfo {
+    /// Create an [`OperationInfoBuilder`], for a `GET /` operation by default.
+    #[must_use]
+    pub fn builder() -> OperationInfoBuilder {
+        OperationInfoBuilder::default()
+    }
+}
+
+impl OperationInfoBuilder {
+    /// Create a new `OperationInfoBuilder`, for a `GET /` operation by default.
+    #[must_use]
+    pub fn new() -> Self {
+        OperationInfoBuilder::default()
+    }
+
+    /// Set the path of the operation, for example `/users/{id}`.
+    #[must_use]
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Set the HTTP method of the operation.
+    #[must_use]
+    pub fn method(mut self, method: Method) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Set the `operationId` of the operation.
+    #[must_use]
+    pub fn operation_id(mut self, operation_id: impl Into<String>) -> Self {
+        self.operation.operation_id = Some(operation_id.into());
+        self
+    }
+
+    /// Set the `summary` of the operation.
+    #[must_use]
+    pub fn summary(mut self, summary: impl Into<String>) -> Self {
+        self.operation.summary = Some(summary.into());
+        self
+    }
+
+    /// Set the `description` of the operation.
+    #[must_use]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.operation.description = Some(description.into());
+        self
+    }
+
+    /// Add a tag to the operation.
+    #[must_use]
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.operation.tags.push(tag.into());
+        self
+    }
+
+    /// Add or replace the response with the `status` code. Use `0` as status for the `default`
+    /// response.
+    #[must_use]
+    pub fn response(mut self, status: u16, response: Response) -> Self {
+        let responses = &mut self.operation.responses;
+        if status == 0 {
+            responses.default = Some(response.into());
+        } else {
+            responses
+                .responses
+                .insert(status.to_string(), response.into());
+        }
+        self
+    }
+
+    /// Add an `application/json` response with the `status` code and the schema of `T`,
+    /// the schema is created using the generator.
+    #[must_use]
+    pub fn json_response<T: JsonSchema>(mut self, gen: &mut OpenApiGenerator, status: u16) -> Self {
+        let schema = gen.json_schema::<T>();
+        add_schema_response(
+            &mut self.operation.responses,
+            status,
+            "application/json",
+            schema,
+        )
+        .expect("Responses added by the builder are never references.");
+        self
+    }
+
+    /// Create the `OperationInfo`.
+    #[must_use]
+    pub fn build(self) -> OperationInfo {
+        OperationInfo {
+            path: self.path,
+            method: self.method,
+            operation: self.operation,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::settings::OpenApiSettings;
+
+    #[derive(JsonSchema)]
+    #[allow(dead_code)]
+    struct User {
+        name: String,
+    }
+
+    #[test]
+    fn test_build_get_operation() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let info = OperationInfo::builder()
+            .path("/users")
+            .method(Method::Get)
+            .summary("Get all users")
+            .description("Get a list of all the users.")
+            .tag("users")
+            .json_response::<Vec<User>>(&mut gen, 200)
+            .build();
+        assert_eq!(info.path, "/users");
+        assert_eq!(info.method, Method::Get);
+        let operation = serde_json::to_value(&info.operation).unwrap();
+        assert_eq!(
+            operation,
+            serde_json::json!({
+                "tags": ["users"],
+                "summary": "Get all users",
+                "description": "Get a list of all the users.",
+                "responses": {
+                    "200": {
+                        "description": "",
+                        "content": {
+                            "application/json": {
+                                "schema": {
+                                    "type": "array",
+                                    "items": {"$ref": "#/components/schemas/User"}
+                                }
+                            }
+                        }
+                    }
+                }
+            })
+        );
+        assert!(gen.definitions().contains_key("User"));
+    }
+}
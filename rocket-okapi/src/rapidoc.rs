@@ -31,6 +31,7 @@
 
 use crate::handlers::{ContentHandler, RedirectHandler};
 use crate::settings::UrlObject;
+use crate::util::escape_html;
 use rocket::http::ContentType;
 use rocket::Route;
 use std::collections::HashMap;
@@ -777,6 +778,58 @@ impl_display!(SchemaHideWriteOnly);
 impl_display!(DefaultSchemaTab);
 impl_display!(ApiKeyLocation);
 
+/// The url of the RapiDoc script that is used by [`rapidoc_handler`],
+/// pinned to the same version as the bundled `rapidoc-min.js`.
+pub const RAPIDOC_SCRIPT_URL: &str = "https://unpkg.com/rapidoc@9.0.0/dist/rapidoc-min.js";
+
+/// The look of the page created by [`rapidoc_handler`].
+#[derive(Debug, Clone)]
+pub struct RapiDocTheme {
+    /// The base theme of the page.
+    ///
+    /// The default is `Theme::Light`.
+    pub theme: Theme,
+    /// How the operations are displayed.
+    ///
+    /// The default is `RenderStyle::View`.
+    pub render_style: RenderStyle,
+}
+
+impl Default for RapiDocTheme {
+    fn default() -> Self {
+        Self {
+            theme: Theme::Light,
+            render_style: RenderStyle::View,
+        }
+    }
+}
+
+/// Create a single HTML page that renders the OpenAPI document at `spec_url` using RapiDoc.
+/// The RapiDoc script is loaded from [`RAPIDOC_SCRIPT_URL`].
+/// Use [`make_rapidoc`] to serve RapiDoc with all the other settings and without a CDN.
+#[must_use]
+pub fn rapidoc_handler(spec_url: &str, theme: RapiDocTheme) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <script type="module" src="{}"></script>
+    <title>API Documentation | RapiDoc</title>
+</head>
+<body>
+    <rapi-doc spec-url="{}" theme="{}" render-style="{}"></rapi-doc>
+</body>
+</html>
+"#,
+        RAPIDOC_SCRIPT_URL,
+        escape_html(spec_url),
+        theme.theme,
+        theme.render_style
+    )
+}
+
 fn slot_list(slots: &[String]) -> String {
     let mut result = "".to_owned();
     for html in slots {
@@ -938,3 +991,22 @@ pub fn make_rapidoc(config: &RapiDocConfig) -> impl Into<Vec<Route>> {
         static_file!("oauth-receiver.html", HTML),
     ]
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rapidoc_handler() {
+        let html = rapidoc_handler(
+            "../openapi.json",
+            RapiDocTheme {
+                theme: Theme::Dark,
+                render_style: RenderStyle::Focused,
+            },
+        );
+        assert!(html.contains(r#"spec-url="../openapi.json""#));
+        assert!(html.contains(r#"theme="dark""#));
+        assert!(html.contains(r#"render-style="focused""#));
+    }
+}
@@ -0,0 +1,63 @@
+//! ### Example
+//! ```rust,no_run
+//! use rocket_okapi::redoc::make_redoc;
+//!
+//! #[rocket::main]
+//! async fn main() {
+//!     rocket::build()
+//!         .mount("/redoc", make_redoc("../openapi.json"))
+//!         .launch()
+//!         .await
+//!         .unwrap();
+//! }
+//! ```
+
+use crate::handlers::ContentHandler;
+use crate::util::escape_html;
+use rocket::http::ContentType;
+use rocket::Route;
+
+/// The url of the Redoc script that is used, pinned to a specific version.
+pub const REDOC_SCRIPT_URL: &str = "https://cdn.redoc.ly/redoc/v2.0.0/bundles/redoc.standalone.js";
+
+/// Create the HTML page that renders the OpenAPI document at `spec_url` using Redoc.
+/// The Redoc script is loaded from [`REDOC_SCRIPT_URL`].
+#[must_use]
+pub fn redoc_handler(spec_url: &str) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <title>API Documentation | Redoc</title>
+</head>
+<body>
+    <redoc spec-url="{}"></redoc>
+    <script src="{}"></script>
+</body>
+</html>
+"#,
+        escape_html(spec_url),
+        REDOC_SCRIPT_URL
+    )
+}
+
+/// Transform the provided `spec_url` into a list of `Route`s that serve Redoc.
+#[must_use]
+pub fn make_redoc(spec_url: &str) -> impl Into<Vec<Route>> {
+    let html = redoc_handler(spec_url);
+    vec![ContentHandler::bytes_owned(ContentType::HTML, html.into_bytes()).into_route("/")]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_redoc_handler() {
+        let html = redoc_handler("../openapi.json?a=1&b=\"2\"");
+        assert!(html.contains(r#"<redoc spec-url="../openapi.json?a=1&amp;b=&quot;2&quot;">"#));
+        assert!(html.contains(&format!(r#"<script src="{}"></script>"#, REDOC_SCRIPT_URL)));
+    }
+}
@@ -135,9 +135,10 @@ impl<'r> OpenApiFromData<'r> for Data<'r> {
 }
 
 // `OpenApiFromForm` is correct, not a mistake, as Rocket requires `FromForm`.
+// Rocket also accepts `multipart/form-data`, but the url encoded form is the common case.
 impl<'r, T: JsonSchema + super::OpenApiFromForm<'r>> OpenApiFromData<'r> for rocket::form::Form<T> {
     fn request_body(gen: &mut OpenApiGenerator) -> Result {
-        fn_request_body!(gen, T, "application/octet-stream")
+        fn_request_body!(gen, T, "application/x-www-form-urlencoded")
     }
 }
 
@@ -155,3 +156,49 @@ impl<'r, T: JsonSchema + Deserialize<'r>> OpenApiFromData<'r>
         fn_request_body!(gen, T, "application/msgpack")
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::settings::OpenApiSettings;
+
+    #[derive(Deserialize, JsonSchema)]
+    #[allow(dead_code)]
+    struct CreateUser {
+        name: String,
+    }
+
+    fn request_body<'r, T: OpenApiFromData<'r>>() -> RequestBody {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        T::request_body(&mut gen).unwrap()
+    }
+
+    #[test]
+    fn test_json() {
+        let body = request_body::<Json<CreateUser>>();
+        assert!(body.required);
+        assert_eq!(
+            serde_json::to_value(&body.content).unwrap(),
+            serde_json::json!({
+                "application/json": {"schema": {"$ref": "#/components/schemas/CreateUser"}}
+            })
+        );
+    }
+
+    #[test]
+    fn test_optional_json() {
+        let body = request_body::<Option<Json<CreateUser>>>();
+        assert!(!body.required);
+        assert!(body.content.contains_key("application/json"));
+    }
+
+    #[test]
+    fn test_form() {
+        let body = request_body::<rocket::form::Form<String>>();
+        assert!(body.required);
+        assert_eq!(
+            body.content.keys().collect::<Vec<_>>(),
+            vec!["application/x-www-form-urlencoded"]
+        );
+    }
+}
@@ -46,6 +46,10 @@ where
             // https://github.com/GREsau/schemars/issues/105
             parameter_list.push(parameter_from_schema(schema, name, required));
         }
+        // An `Option<T>` in OpenAPI 3.1, like `type: ["integer", "null"]`.
+        Some(SingleOrVec::Vec(instance_types)) if instance_types.contains(&InstanceType::Null) => {
+            parameter_list.push(parameter_from_schema(schema, name, required));
+        }
         _ => {
             // TODO: Do nothing for now, might need implementation later.
             log::warn!(
@@ -68,6 +72,11 @@ fn parameter_from_schema(schema: SchemaObject, name: String, mut required: bool)
                 }
             }
         }
+        if let Some(SingleOrVec::Vec(instance_types)) = &schema.instance_type {
+            if instance_types.contains(&InstanceType::Null) {
+                required = false;
+            }
+        }
     }
     let description = schema.metadata.as_ref().and_then(|m| m.description.clone());
     Parameter {
@@ -88,3 +97,40 @@ fn parameter_from_schema(schema: SchemaObject, name: String, mut required: bool)
         extensions: Object::default(),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::settings::{OpenApiSettings, OpenApiVersion};
+
+    fn query_parameter<T: JsonSchema>(settings: &OpenApiSettings) -> serde_json::Value {
+        let mut gen = OpenApiGenerator::new(settings);
+        let parameters = get_nested_form_parameters::<T>(&mut gen, "page".to_owned(), true);
+        assert_eq!(parameters.len(), 1);
+        serde_json::to_value(&parameters[0]).unwrap()
+    }
+
+    #[test]
+    fn test_required_query_parameter() {
+        let parameter = query_parameter::<u32>(&OpenApiSettings::default());
+        assert_eq!(parameter["in"], "query");
+        assert_eq!(parameter["required"], true);
+        assert_eq!(parameter["schema"]["type"], "integer");
+    }
+
+    #[test]
+    fn test_optional_query_parameter() {
+        let parameter = query_parameter::<Option<u32>>(&OpenApiSettings::default());
+        assert_eq!(parameter["name"], "page");
+        assert!(parameter.get("required").is_none());
+        assert_eq!(parameter["schema"]["type"], "integer");
+
+        let settings = OpenApiSettings::default().with_version(OpenApiVersion::V3_1);
+        let parameter = query_parameter::<Option<u32>>(&settings);
+        assert!(parameter.get("required").is_none());
+        assert_eq!(
+            parameter["schema"]["type"],
+            serde_json::json!(["integer", "null"])
+        );
+    }
+}
@@ -30,3 +30,33 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::request::OpenApiFromSegments;
+    use crate::settings::OpenApiSettings;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_path_parameter() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let parameter = u32::path_parameter(&mut gen, "id".to_owned()).unwrap();
+        let parameter = serde_json::to_value(&parameter).unwrap();
+        assert_eq!(parameter["name"], "id");
+        assert_eq!(parameter["in"], "path");
+        assert_eq!(parameter["required"], true);
+        assert_eq!(parameter["schema"]["type"], "integer");
+    }
+
+    #[test]
+    fn test_segments_parameter() {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        let parameter = PathBuf::path_multi_parameter(&mut gen, "path".to_owned()).unwrap();
+        let parameter = serde_json::to_value(&parameter).unwrap();
+        assert_eq!(parameter["in"], "path");
+        assert_eq!(parameter["required"], true);
+        assert_eq!(parameter["allowReserved"], true);
+        assert_eq!(parameter["schema"]["type"], "string");
+    }
+}
@@ -67,7 +67,7 @@ impl OpenApiResponderInner for () {
     }
 }
 
-impl<'r, 'o: 'r, T> OpenApiResponderInner for std::borrow::Cow<'o, T>
+impl<'o, T> OpenApiResponderInner for std::borrow::Cow<'o, T>
 where
     T: OpenApiResponderInner + Clone,
 {
@@ -86,7 +86,7 @@ impl<T: OpenApiResponderInner> OpenApiResponderInner for Option<T> {
     }
 }
 
-impl<'r, 'o, T, E> OpenApiResponderInner for std::result::Result<T, E>
+impl<T, E> OpenApiResponderInner for std::result::Result<T, E>
 where
     T: OpenApiResponderInner,
     E: OpenApiResponderInner,
@@ -98,7 +98,7 @@ where
     }
 }
 
-impl<'r, 'o, L, R> OpenApiResponderInner for either::Either<L, R>
+impl<L, R> OpenApiResponderInner for either::Either<L, R>
 where
     L: OpenApiResponderInner,
     R: OpenApiResponderInner,
@@ -345,3 +345,59 @@ impl<T: Serialize + JsonSchema + Send> OpenApiResponderInner
         Ok(responses)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::settings::OpenApiSettings;
+
+    #[derive(Serialize, JsonSchema)]
+    struct User {
+        name: String,
+    }
+
+    fn responses<T: OpenApiResponderInner>() -> serde_json::Value {
+        let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+        serde_json::to_value(T::responses(&mut gen).unwrap()).unwrap()
+    }
+
+    fn user_response() -> serde_json::Value {
+        serde_json::json!({
+            "description": "",
+            "content": {
+                "application/json": {
+                    "schema": {"$ref": "#/components/schemas/User"}
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            responses::<Json<User>>(),
+            serde_json::json!({"200": user_response()})
+        );
+    }
+
+    #[test]
+    fn test_option() {
+        assert_eq!(
+            responses::<Option<Json<User>>>(),
+            serde_json::json!({
+                "200": user_response(),
+                "404": {"description": ""}
+            })
+        );
+    }
+
+    #[test]
+    fn test_result() {
+        let io_error = responses::<std::result::Result<Json<User>, std::io::Error>>();
+        assert_eq!(io_error["200"], user_response());
+        assert!(io_error.get("500").is_some());
+        let status = responses::<std::result::Result<Json<User>, rocket::http::Status>>();
+        assert_eq!(status["200"], user_response());
+        assert!(status.get("default").is_some());
+    }
+}
@@ -0,0 +1,253 @@
+use okapi::openapi3::{OAuthFlows, Object, SecurityScheme, SecuritySchemeData};
+use okapi::Map;
+
+/// Builder to create an OAuth2 or cookie [`SecurityScheme`], which can be added using
+/// [`OpenApiGenerator::add_security_scheme`](crate::gen::OpenApiGenerator::add_security_scheme).
+///
+/// ```rust
+/// use rocket_okapi::gen::OpenApiGenerator;
+/// use rocket_okapi::settings::OpenApiSettings;
+/// use rocket_okapi::SecuritySchemeBuilder;
+///
+/// let mut gen = OpenApiGenerator::new(&OpenApiSettings::default());
+/// let scheme = SecuritySchemeBuilder::oauth2_authorization_code(
+///     "https://example.com/oauth/authorize",
+///     "https://example.com/oauth/token",
+/// )
+/// .scope("read", "Read your data")
+/// .scope("write", "Change your data")
+/// .build();
+/// gen.add_security_scheme("oauth".to_owned(), scheme);
+/// ```
+#[derive(Debug, Clone)]
+pub struct SecuritySchemeBuilder {
+    description: Option<String>,
+    data: SecuritySchemeData,
+}
+
+impl SecuritySchemeBuilder {
+    /// Create a builder for the `authorizationCode` flow.
+    #[must_use]
+    pub fn oauth2_authorization_code(
+        authorization_url: impl Into<String>,
+        token_url: impl Into<String>,
+    ) -> Self {
+        Self::from_flows(OAuthFlows::AuthorizationCode {
+            authorization_url: authorization_url.into(),
+            token_url: token_url.into(),
+            refresh_url: None,
+            scopes: Map::new(),
+            extensions: Object::default(),
+        })
+    }
+
+    /// Create a builder for the `implicit` flow.
+    #[must_use]
+    pub fn oauth2_implicit(authorization_url: impl Into<String>) -> Self {
+        Self::from_flows(OAuthFlows::Implicit {
+            authorization_url: authorization_url.into(),
+            refresh_url: None,
+            scopes: Map::new(),
+            extensions: Object::default(),
+        })
+    }
+
+    /// Create a builder for the `password` flow.
+    #[must_use]
+    pub fn oauth2_password(token_url: impl Into<String>) -> Self {
+        Self::from_flows(OAuthFlows::Password {
+            token_url: token_url.into(),
+            refresh_url: None,
+            scopes: Map::new(),
+            extensions: Object::default(),
+        })
+    }
+
+    /// Create a builder for the `clientCredentials` flow.
+    #[must_use]
+    pub fn oauth2_client_credentials(token_url: impl Into<String>) -> Self {
+        Self::from_flows(OAuthFlows::ClientCredentials {
+            token_url: token_url.into(),
+            refresh_url: None,
+            scopes: Map::new(),
+            extensions: Object::default(),
+        })
+    }
+
+    /// Create a builder for an `apiKey` scheme with the key in the cookie with the `name`, like a
+    /// session cookie. Notes about the cookie, like its `SameSite` attribute, can be added using
+    /// [`description`](Self::description).
+    ///
+    /// Browsers do not allow JavaScript to set the `Cookie` header, so the "Authorize" dialog of
+    /// Swagger UI can not set the cookie. A cookie that is already in the browser is sent with
+    /// "Try it out" requests to the same origin, and to other origins when
+    /// `SwaggerUIConfig::with_credentials` is set.
+    #[must_use]
+    pub fn api_key_cookie(name: impl Into<String>) -> Self {
+        SecuritySchemeBuilder {
+            description: None,
+            data: SecuritySchemeData::ApiKey {
+                name: name.into(),
+                location: "cookie".to_owned(),
+            },
+        }
+    }
+
+    fn from_flows(flows: OAuthFlows) -> Self {
+        SecuritySchemeBuilder {
+            description: None,
+            data: SecuritySchemeData::OAuth2 { flows },
+        }
+    }
+
+    /// Add a scope with the `name` and a short `description` to the flow.
+    /// This is ignored for schemes that are not OAuth2.
+    #[must_use]
+    pub fn scope(mut self, name: impl Into<String>, description: impl Into<String>) -> Self {
+        if let Some(scopes) = self.scopes_mut() {
+            scopes.insert(name.into(), description.into());
+        }
+        self
+    }
+
+    /// Set the `refreshUrl` of the flow.
+    /// This is ignored for schemes that are not OAuth2.
+    #[must_use]
+    pub fn refresh_url(mut self, refresh_url: impl Into<String>) -> Self {
+        if let Some(current) = self.refresh_url_mut() {
+            *current = Some(refresh_url.into());
+        }
+        self
+    }
+
+    /// Set the `description` of the security scheme.
+    #[must_use]
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Create the `SecurityScheme`.
+    #[must_use]
+    pub fn build(self) -> SecurityScheme {
+        SecurityScheme {
+            description: self.description,
+            data: self.data,
+            extensions: Object::default(),
+        }
+    }
+
+    fn scopes_mut(&mut self) -> Option<&mut Map<String, String>> {
+        match &mut self.data {
+            SecuritySchemeData::OAuth2 { flows } => match flows {
+                OAuthFlows::Implicit { scopes, .. }
+                | OAuthFlows::Password { scopes, .. }
+                | OAuthFlows::ClientCredentials { scopes, .. }
+                | OAuthFlows::AuthorizationCode { scopes, .. } => Some(scopes),
+            },
+            _ => None,
+        }
+    }
+
+    fn refresh_url_mut(&mut self) -> Option<&mut Option<String>> {
+        match &mut self.data {
+            SecuritySchemeData::OAuth2 { flows } => match flows {
+                OAuthFlows::Implicit { refresh_url, .. }
+                | OAuthFlows::Password { refresh_url, .. }
+                | OAuthFlows::ClientCredentials { refresh_url, .. }
+                | OAuthFlows::AuthorizationCode { refresh_url, .. } => Some(refresh_url),
+            },
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_authorization_code() {
+        let scheme = SecuritySchemeBuilder::oauth2_authorization_code(
+            "https://example.com/oauth/authorize",
+            "https://example.com/oauth/token",
+        )
+        .scope("read", "Read your data")
+        .scope("write", "Change your data")
+        .description("Log in with your account.")
+        .build();
+        assert_eq!(
+            serde_json::to_value(&scheme).unwrap(),
+            serde_json::json!({
+                "type": "oauth2",
+                "description": "Log in with your account.",
+                "flows": {
+                    "authorizationCode": {
+                        "authorizationUrl": "https://example.com/oauth/authorize",
+                        "tokenUrl": "https://example.com/oauth/token",
+                        "scopes": {
+                            "read": "Read your data",
+                            "write": "Change your data"
+                        }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_api_key_cookie() {
+        let scheme = SecuritySchemeBuilder::api_key_cookie("sid")
+            .description("The session cookie, it is set with `SameSite=Lax`.")
+            .scope("read", "Ignored")
+            .build();
+        assert_eq!(
+            serde_json::to_value(&scheme).unwrap(),
+            serde_json::json!({
+                "type": "apiKey",
+                "name": "sid",
+                "in": "cookie",
+                "description": "The session cookie, it is set with `SameSite=Lax`."
+            })
+        );
+    }
+
+    #[test]
+    fn test_other_flows() {
+        let flow = |builder: SecuritySchemeBuilder| {
+            serde_json::to_value(builder.refresh_url("https://example.com/refresh").build())
+                .unwrap()["flows"]
+                .clone()
+        };
+        assert_eq!(
+            flow(SecuritySchemeBuilder::oauth2_implicit(
+                "https://example.com/auth"
+            )),
+            serde_json::json!({"implicit": {
+                "authorizationUrl": "https://example.com/auth",
+                "refreshUrl": "https://example.com/refresh",
+                "scopes": {}
+            }})
+        );
+        assert_eq!(
+            flow(SecuritySchemeBuilder::oauth2_password(
+                "https://example.com/token"
+            )),
+            serde_json::json!({"password": {
+                "tokenUrl": "https://example.com/token",
+                "refreshUrl": "https://example.com/refresh",
+                "scopes": {}
+            }})
+        );
+        assert_eq!(
+            flow(SecuritySchemeBuilder::oauth2_client_credentials(
+                "https://example.com/token"
+            )),
+            serde_json::json!({"clientCredentials": {
+                "tokenUrl": "https://example.com/token",
+                "refreshUrl": "https://example.com/refresh",
+                "scopes": {}
+            }})
+        );
+    }
+}
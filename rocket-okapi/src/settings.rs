@@ -1,25 +1,333 @@
+use crate::visit::SchemaVisitor;
+use okapi::openapi3::{Info, Response};
 use schemars::gen::SchemaSettings;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
 
-/// Settings which are used to customize the behavior of the `OpenApiGenerator`.
+/// The version of the OpenAPI specification the document is generated for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OpenApiVersion {
+    /// OpenAPI version `3.0.0`.
+    #[default]
+    V3_0,
+    /// OpenAPI version `3.1.0`, aligned with JSON Schema 2020-12.
+    V3_1,
+}
+
+impl OpenApiVersion {
+    /// The version string used in the `openapi` field of the document.
+    #[must_use]
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OpenApiVersion::V3_0 => "3.0.0",
+            OpenApiVersion::V3_1 => "3.1.0",
+        }
+    }
+
+    /// Create the `SchemaSettings` that generate schemas matching this version.
+    #[must_use]
+    pub fn schema_settings(&self) -> SchemaSettings {
+        match self {
+            OpenApiVersion::V3_0 => SchemaSettings::openapi3(),
+            OpenApiVersion::V3_1 => SchemaSettings::draft2019_09().with(|s| {
+                s.definitions_path = "#/components/schemas/".to_owned();
+                s.meta_schema = None;
+            }),
+        }
+    }
+}
+
+/// How operations for the `CONNECT` HTTP method are handled.
+/// OpenAPI has no field for `CONNECT` operations in a `PathItem`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ConnectHandling {
+    /// Leave the operation out of the document and add a warning to the `OpenApiGenerator`.
+    #[default]
+    Drop,
+    /// Add the operation to the `x-connect` extension of the `PathItem`.
+    Extension,
+    /// Return an [`OpenApiError::UnsupportedMethod`](crate::OpenApiError::UnsupportedMethod)
+    /// when the operation is added.
+    Error,
+}
+
+/// What to do when two operations end up with the same `operationId`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OperationIdCollision {
+    /// Keep the `operationId` of the first operation, and add a numeric suffix to the others,
+    /// like `get_user_2`.
+    #[default]
+    Suffix,
+    /// Keep the `operationId`s and add an
+    /// [`OpenApiError::DuplicateOperationId`](crate::OpenApiError::DuplicateOperationId)
+    /// warning, this is an error for
+    /// [`into_openapi_strict`](crate::gen::OpenApiGenerator::into_openapi_strict).
+    Error,
+}
+
+/// What [`OpenApiGenerator::add_operation`](crate::gen::OpenApiGenerator::add_operation) does
+/// when an operation was already added for the same path and method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CollisionPolicy {
+    /// Panic, for example to catch overlapping routes in tests.
+    Panic,
+    /// Keep the operation that was added first and log a warning.
+    KeepFirst,
+    /// Replace the operation with the one that is added last and log a warning.
+    /// This is allowed by Rocket when a ranking is given, for example:
+    /// `#[get("/user", rank = 2)]`.
+    #[default]
+    KeepLast,
+    /// Keep the operation that was added first and add an
+    /// [`OpenApiWarning::OperationError`](crate::OpenApiWarning::OperationError) warning.
+    Error,
+}
+
+/// What to do with a trailing slash in the path of an added operation, like `/users/`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrailingSlash {
+    /// Keep the trailing slash, so `/users/` and `/users` are different paths.
+    #[default]
+    Keep,
+    /// Remove the trailing slash, so the operations of `/users/` and `/users` are in the same
+    /// `PathItem`. The root path `/` is kept.
+    Strip,
+}
+
+/// How the schemas in `components.schemas` are named.
+#[derive(Clone, Default)]
+pub enum SchemaNaming {
+    /// Use the name of the type, like `Config`. When two types have the same name schemars adds
+    /// a number to the second one, like `Config2`.
+    #[default]
+    TypeName,
+    /// Use the full path of the type with `.` as separator, like `my_crate.settings.Config`.
+    /// Characters that are not allowed in component names, like `<`, are replaced with `_`.
+    FullPath,
+    /// Create the name from the full path of the type, like `my_crate::settings::Config`.
+    Custom(Arc<dyn Fn(&str) -> String + Send + Sync>),
+}
+
+impl SchemaNaming {
+    /// Get the name for the schema of the type with the `schema_id`, or `None` when the name
+    /// from schemars is used.
+    pub(crate) fn schema_key(&self, schema_id: &str) -> Option<String> {
+        match self {
+            SchemaNaming::TypeName => None,
+            SchemaNaming::FullPath => Some(
+                schema_id
+                    .replace("::", ".")
+                    .chars()
+                    .map(|c| {
+                        if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                            c
+                        } else {
+                            '_'
+                        }
+                    })
+                    .collect(),
+            ),
+            SchemaNaming::Custom(f) => Some(f(schema_id)),
+        }
+    }
+}
+
+impl fmt::Debug for SchemaNaming {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaNaming::TypeName => f.write_str("TypeName"),
+            SchemaNaming::FullPath => f.write_str("FullPath"),
+            SchemaNaming::Custom(_) => f.write_str("Custom(Fn(&str) -> String)"),
+        }
+    }
+}
+
+/// The UI used to render the documentation at [`OpenApiSettings::docs_path`].
 #[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum DocRenderer {
+    /// Use [Redoc](https://github.com/Redocly/redoc), see [`crate::redoc`].
+    #[cfg(feature = "redoc")]
+    Redoc,
+    /// Use [RapiDoc](https://mrin9.github.io/RapiDoc/), see [`crate::rapidoc::rapidoc_handler`].
+    #[cfg(feature = "rapidoc")]
+    RapiDoc(crate::rapidoc::RapiDocTheme),
+}
+
+impl DocRenderer {
+    /// Create the HTML page that renders the OpenAPI document at `spec_url`.
+    #[must_use]
+    #[allow(unused_variables)] // When none of the renderer features are enabled.
+    pub fn html(&self, spec_url: &str) -> String {
+        match *self {
+            #[cfg(feature = "redoc")]
+            DocRenderer::Redoc => crate::redoc::redoc_handler(spec_url),
+            #[cfg(feature = "rapidoc")]
+            DocRenderer::RapiDoc(ref theme) => {
+                crate::rapidoc::rapidoc_handler(spec_url, theme.clone())
+            }
+        }
+    }
+}
+
+/// Function that creates the `operationId` from the path of the route function.
+pub type OperationIdFn = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// Settings which are used to customize the behavior of the `OpenApiGenerator`.
+#[derive(Clone)]
 pub struct OpenApiSettings {
+    /// The version of the OpenAPI specification to generate.
+    /// Use [`OpenApiSettings::with_version`] to also change the `schema_settings` to match.
+    /// Default: `OpenApiVersion::V3_0`.
+    pub version: OpenApiVersion,
+    /// The `info` of the generated document, like the title, version, contact and license.
+    /// When this is left empty the `openapi_spec!` and `openapi_get_routes!` macros (and similar)
+    /// use the info from `Cargo.toml` instead. Default: empty `Info`.
+    pub info: Info,
     /// Settings to customize how JSON Schemas are generated.
     pub schema_settings: SchemaSettings,
+    /// How the schemas in `components.schemas` are named. This applies to the types passed to
+    /// [`OpenApiGenerator::json_schema`], like the types of request bodies and responses. Their
+    /// schemas are named when they are generated, so the definitions and the `$ref`s use the
+    /// new name right away. Types that are only used by the fields of other types keep the name
+    /// from schemars, schemars does not tell the generator what their paths are. This is also
+    /// the case for a type that was used by a field before it is passed to `json_schema`.
+    /// Default: `SchemaNaming::TypeName`.
+    ///
+    /// [`OpenApiGenerator::json_schema`]: crate::gen::OpenApiGenerator::json_schema
+    pub schema_naming: SchemaNaming,
+    /// When set to `true` the name of every schema in `components.schemas` is used as its
+    /// `title` when it has no `title`, some UIs like Redoc show this as the name of the schema.
+    /// Default: `false`.
+    pub schema_titles_from_name: bool,
     /// The path to the json file that contains the API specification. Then default is
     /// `openapi.json`.
     pub json_path: String,
+    /// The path to the yaml file that contains the API specification.
+    /// When set to `None` the yaml file is not served. Default: `None`.
+    #[cfg(feature = "yaml")]
+    pub yaml_path: Option<String>,
+    /// The UI used to render the documentation. When set to `None` no documentation UI is
+    /// served. Default: `None`.
+    pub doc_renderer: Option<DocRenderer>,
+    /// The path to the documentation UI, when `doc_renderer` is set. Default: `/docs`.
+    pub docs_path: String,
+    /// When set to `true`, adding an operation that is identical to an already added operation
+    /// (same path, method and content) is treated as a no-op instead of a duplicate.
+    /// Default: `false`.
+    pub ignore_identical_operations: bool,
+    /// How operations for the `CONNECT` HTTP method are handled.
+    /// Default: `ConnectHandling::Drop`.
+    pub connect_handling: ConnectHandling,
+    /// Function used to create the `operationId` from the path of the route function,
+    /// for example `my::module::handler`.
+    /// When set to `None` the leading `::` is removed and all `::` are replaced with `_`,
+    /// for example `my_module_handler`. Default: `None`.
+    pub operation_id_fn: Option<OperationIdFn>,
+    /// What to do when two operations have the same `operationId`.
+    /// Default: `OperationIdCollision::Suffix`.
+    pub operation_id_collision: OperationIdCollision,
+    /// Response added to operations that have no responses, with its status code like
+    /// `200` or `default`. OpenAPI requires every operation to have at least one response.
+    /// Default: `None`.
+    pub default_response: Option<(String, Response)>,
+    /// When set to `true` the responses added using
+    /// [`OpenApiGenerator::add_catcher_response`] are added to every operation that does not
+    /// have a response with the same status code. When set to `false` they are only added to
+    /// `components.responses`. Default: `true`.
+    ///
+    /// [`OpenApiGenerator::add_catcher_response`]: crate::gen::OpenApiGenerator::add_catcher_response
+    pub inject_catcher_responses: bool,
+    /// Visitors that change every schema in the document, called in order after the document is
+    /// generated. Default: empty.
+    pub schema_visitors: Vec<Arc<dyn SchemaVisitor>>,
+    /// When set to `true` the `openapi_spec!` and `openapi_get_routes!` macros (and similar)
+    /// generate the document using [`OpenApiGenerator::into_openapi_strict`] and panic when
+    /// the document is incomplete. Default: `false`.
+    ///
+    /// [`OpenApiGenerator::into_openapi_strict`]: crate::gen::OpenApiGenerator::into_openapi_strict
+    pub strict: bool,
+    /// Prefix added to every path in the document, for example `/api/v2` when the API is
+    /// mounted under `/api/v2`. The prefixed paths are normalized like the added paths, see
+    /// `trailing_slash`. When two operations end up with the same path and method only the first
+    /// one is documented, the other one is reported with an `OpenApiWarning::OperationError`.
+    /// Default: `None`.
+    pub base_path: Option<String>,
+    /// What `OpenApiGenerator::add_operation` does when an operation was already added for the
+    /// same path and method. Default: `CollisionPolicy::KeepLast`.
+    pub on_collision: CollisionPolicy,
+    /// What to do with a trailing slash in the path of an added operation. Repeated slashes in
+    /// the path, like in `/users//{id}`, are always replaced with a single slash. The paths given
+    /// to the other methods of `OpenApiGenerator`, like `remove_operation`, are changed in the
+    /// same way. Default: `TrailingSlash::Keep`.
+    pub trailing_slash: TrailingSlash,
 }
 
 impl Default for OpenApiSettings {
     fn default() -> Self {
         OpenApiSettings {
+            version: OpenApiVersion::default(),
+            info: Info::default(),
             schema_settings: SchemaSettings::openapi3(),
+            schema_naming: SchemaNaming::default(),
+            schema_titles_from_name: false,
             json_path: "/openapi.json".to_owned(),
+            #[cfg(feature = "yaml")]
+            yaml_path: None,
+            doc_renderer: None,
+            docs_path: "/docs".to_owned(),
+            ignore_identical_operations: false,
+            connect_handling: ConnectHandling::default(),
+            operation_id_fn: None,
+            operation_id_collision: OperationIdCollision::default(),
+            default_response: None,
+            inject_catcher_responses: true,
+            schema_visitors: Vec::new(),
+            strict: false,
+            base_path: None,
+            on_collision: CollisionPolicy::default(),
+            trailing_slash: TrailingSlash::default(),
         }
     }
 }
 
+impl fmt::Debug for OpenApiSettings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("OpenApiSettings");
+        debug
+            .field("version", &self.version)
+            .field("info", &self.info)
+            .field("schema_settings", &self.schema_settings)
+            .field("schema_naming", &self.schema_naming)
+            .field("schema_titles_from_name", &self.schema_titles_from_name)
+            .field("json_path", &self.json_path);
+        #[cfg(feature = "yaml")]
+        debug.field("yaml_path", &self.yaml_path);
+        debug
+            .field("doc_renderer", &self.doc_renderer)
+            .field("docs_path", &self.docs_path)
+            .field(
+                "ignore_identical_operations",
+                &self.ignore_identical_operations,
+            )
+            .field("connect_handling", &self.connect_handling)
+            .field(
+                "operation_id_fn",
+                &self.operation_id_fn.as_ref().map(|_| "Fn(&str) -> String"),
+            )
+            .field("operation_id_collision", &self.operation_id_collision)
+            .field("default_response", &self.default_response)
+            .field("inject_catcher_responses", &self.inject_catcher_responses)
+            .field("schema_visitors", &self.schema_visitors.len())
+            .field("strict", &self.strict)
+            .field("base_path", &self.base_path)
+            .field("on_collision", &self.on_collision)
+            .field("trailing_slash", &self.trailing_slash)
+            .finish()
+    }
+}
+
 impl OpenApiSettings {
     /// Create a new instance of `OpenApiSettings`. Equivalent to calling `Default::default`.
     #[must_use]
@@ -28,6 +336,49 @@ impl OpenApiSettings {
             ..OpenApiSettings::default()
         }
     }
+
+    /// Set the OpenAPI version to generate and replace the `schema_settings` with the settings
+    /// that match this version.
+    #[must_use]
+    pub fn with_version(mut self, version: OpenApiVersion) -> Self {
+        self.version = version;
+        self.schema_settings = version.schema_settings();
+        self
+    }
+
+    /// Set the prefix of the references to schemas, for example `#/definitions/`.
+    /// This changes `schema_settings.definitions_path`, so should be called after
+    /// [`with_version`](Self::with_version). The schemas are still added to
+    /// `components.schemas` using the same names. Default: `#/components/schemas/`.
+    #[must_use]
+    pub fn with_definitions_path(mut self, definitions_path: impl Into<String>) -> Self {
+        self.schema_settings.definitions_path = definitions_path.into();
+        self
+    }
+
+    /// Change the `schema_settings` using the function `f`, for example to turn off
+    /// `option_nullable` or to set `inline_subschemas`. When combined with
+    /// [`with_version`](Self::with_version) this should be called after it, because that
+    /// replaces the `schema_settings`.
+    #[must_use]
+    pub fn with_schema_settings<F: FnOnce(&mut SchemaSettings)>(mut self, f: F) -> Self {
+        f(&mut self.schema_settings);
+        self
+    }
+
+    /// Add a visitor to [`schema_visitors`](Self::schema_visitors).
+    #[must_use]
+    pub fn with_schema_visitor(mut self, visitor: impl SchemaVisitor + 'static) -> Self {
+        self.schema_visitors.push(Arc::new(visitor));
+        self
+    }
+
+    /// Set the `info` of the generated document.
+    #[must_use]
+    pub fn with_info(mut self, info: Info) -> Self {
+        self.info = info;
+        self
+    }
 }
 
 /// Contains a named url.
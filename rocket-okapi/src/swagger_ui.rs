@@ -104,6 +104,11 @@ pub struct SwaggerUIConfig {
     /// `minimum`) fields and values for Parameters.
     /// Default: `false`.
     pub show_common_extensions: bool,
+    // network options:
+    /// If set to true, "Try it out" requests to other origins include credentials, like the
+    /// cookies of an `apiKey` security scheme in a cookie.
+    /// Default: `false`.
+    pub with_credentials: bool,
 }
 
 impl Default for SwaggerUIConfig {
@@ -122,6 +127,7 @@ impl Default for SwaggerUIConfig {
             max_displayed_tags: 0,
             show_extensions: false,
             show_common_extensions: false,
+            with_credentials: false,
         }
     }
 }
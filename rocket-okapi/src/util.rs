@@ -1,6 +1,8 @@
 use crate::{OpenApiError, Result};
 use okapi::openapi3::{MediaType, RefOr, Response, Responses, SchemaObject};
 use okapi::Map;
+use schemars::schema::{InstanceType, Schema, SingleOrVec};
+use serde_json::Value;
 
 // FIXME this whole file is a huge mess...
 
@@ -107,8 +109,8 @@ pub fn add_media_type(
 
 /// Replaces the Content-Type for all responses with `content_type`.
 pub fn set_content_type(responses: &mut Responses, content_type: impl ToString) -> Result<()> {
-    for ref mut resp_refor in responses.responses.values_mut() {
-        let response = ensure_not_ref(*resp_refor)?;
+    for resp_refor in responses.responses.values_mut() {
+        let response = ensure_not_ref(resp_refor)?;
         let content = &mut response.content;
         let mt = if content.values().len() == 1 {
             content.values().next().unwrap().clone()
@@ -221,3 +223,102 @@ fn accept_either_schema(
     schema.subschemas().any_of = Some(vec![s1.into(), s2.into()]);
     Some(schema)
 }
+
+/// Escape the characters that have a special meaning in HTML, so `text` can be used in
+/// an HTML attribute or element.
+#[cfg(any(feature = "redoc", feature = "rapidoc"))]
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            c => result.push(c),
+        }
+    }
+    result
+}
+
+/// Check if the `value` matches the `schema`, references are resolved using the `definitions`.
+/// Only the types, required properties, properties, items and subschemas are checked.
+pub(crate) fn value_matches_schema(
+    value: &Value,
+    schema: &SchemaObject,
+    definitions: &Map<String, Schema>,
+    prefix: &str,
+) -> bool {
+    let matches = |value: &Value, schema: &Schema| match schema {
+        Schema::Bool(b) => *b,
+        Schema::Object(schema) => value_matches_schema(value, schema, definitions, prefix),
+    };
+    if let Some(reference) = &schema.reference {
+        return match reference
+            .strip_prefix(prefix)
+            .and_then(|name| definitions.get(name))
+        {
+            Some(definition) => matches(value, definition),
+            // Can not check unknown references.
+            None => true,
+        };
+    }
+    // Used by OpenAPI 3.0 instead of a `null` type.
+    if value.is_null() && schema.extensions.get("nullable") == Some(&Value::Bool(true)) {
+        return true;
+    }
+    if let Some(instance_type) = &schema.instance_type {
+        let types: &[InstanceType] = match instance_type {
+            SingleOrVec::Single(instance_type) => std::slice::from_ref(instance_type),
+            SingleOrVec::Vec(types) => types,
+        };
+        let type_matches = types.iter().any(|instance_type| match instance_type {
+            InstanceType::Null => value.is_null(),
+            InstanceType::Boolean => value.is_boolean(),
+            InstanceType::Object => value.is_object(),
+            InstanceType::Array => value.is_array(),
+            InstanceType::Number => value.is_number(),
+            InstanceType::String => value.is_string(),
+            InstanceType::Integer => value.is_i64() || value.is_u64(),
+        });
+        if !type_matches {
+            return false;
+        }
+    }
+    if let Some(subschemas) = &schema.subschemas {
+        if let Some(all_of) = &subschemas.all_of {
+            if !all_of.iter().all(|schema| matches(value, schema)) {
+                return false;
+            }
+        }
+        for any_of in [&subschemas.any_of, &subschemas.one_of]
+            .iter()
+            .filter_map(|any_of| any_of.as_ref())
+        {
+            if !any_of.iter().any(|schema| matches(value, schema)) {
+                return false;
+            }
+        }
+    }
+    if let (Some(object), Value::Object(map)) = (&schema.object, value) {
+        if !object.required.iter().all(|name| map.contains_key(name)) {
+            return false;
+        }
+        for (name, value) in map {
+            if let Some(property) = object.properties.get(name) {
+                if !matches(value, property) {
+                    return false;
+                }
+            }
+        }
+    }
+    if let (Some(array), Value::Array(list)) = (&schema.array, value) {
+        if let Some(SingleOrVec::Single(items)) = &array.items {
+            if !list.iter().all(|value| matches(value, items)) {
+                return false;
+            }
+        }
+    }
+    true
+}
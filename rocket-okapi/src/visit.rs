@@ -0,0 +1,406 @@
+use okapi::openapi3::{
+    Components, MediaType, OpenApi, Operation, Parameter, ParameterValue, PathItem, RefOr,
+    RequestBody, Response,
+};
+use okapi::Map;
+use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec, SubschemaValidation};
+use schemars::visit::{visit_schema_object, Visitor};
+
+/// Visit every schema in the `OpenApi` document, this includes the component schemas and all
+/// the schemas used inline by the operations.
+pub fn visit_openapi<V: Visitor + ?Sized>(v: &mut V, spec: &mut OpenApi) {
+    for path_item in spec.paths.values_mut().chain(spec.webhooks.values_mut()) {
+        visit_path_item(v, path_item);
+    }
+    if let Some(components) = &mut spec.components {
+        visit_components(v, components);
+    }
+}
+
+/// Visit every schema in the `Components`.
+pub fn visit_components<V: Visitor + ?Sized>(v: &mut V, components: &mut Components) {
+    for schema in components.schemas.values_mut() {
+        v.visit_schema_object(schema);
+    }
+    for response in components.responses.values_mut() {
+        if let RefOr::Object(response) = response {
+            visit_response(v, response);
+        }
+    }
+    for parameter in components.parameters.values_mut() {
+        if let RefOr::Object(parameter) = parameter {
+            visit_parameter(v, parameter);
+        }
+    }
+    for request_body in components.request_bodies.values_mut() {
+        if let RefOr::Object(request_body) = request_body {
+            visit_request_body(v, request_body);
+        }
+    }
+}
+
+/// Visit every schema used by the operations of the `PathItem`.
+pub fn visit_path_item<V: Visitor + ?Sized>(v: &mut V, path_item: &mut PathItem) {
+    for parameter in &mut path_item.parameters {
+        if let RefOr::Object(parameter) = parameter {
+            visit_parameter(v, parameter);
+        }
+    }
+    let operations = vec![
+        &mut path_item.get,
+        &mut path_item.put,
+        &mut path_item.post,
+        &mut path_item.delete,
+        &mut path_item.options,
+        &mut path_item.head,
+        &mut path_item.patch,
+        &mut path_item.trace,
+    ];
+    for operation in operations.into_iter().flatten() {
+        visit_operation(v, operation);
+    }
+}
+
+/// Visit every schema used by the `Operation`.
+pub fn visit_operation<V: Visitor + ?Sized>(v: &mut V, operation: &mut Operation) {
+    for parameter in &mut operation.parameters {
+        if let RefOr::Object(parameter) = parameter {
+            visit_parameter(v, parameter);
+        }
+    }
+    if let Some(RefOr::Object(request_body)) = &mut operation.request_body {
+        visit_request_body(v, request_body);
+    }
+    if let Some(RefOr::Object(response)) = &mut operation.responses.default {
+        visit_response(v, response);
+    }
+    for response in operation.responses.responses.values_mut() {
+        if let RefOr::Object(response) = response {
+            visit_response(v, response);
+        }
+    }
+    for callback in operation.callbacks.values_mut() {
+        if let RefOr::Object(callback) = callback {
+            for path_item in callback.callbacks.values_mut() {
+                visit_path_item(v, path_item);
+            }
+        }
+    }
+}
+
+pub(crate) fn visit_parameter<V: Visitor + ?Sized>(v: &mut V, parameter: &mut Parameter) {
+    match &mut parameter.value {
+        ParameterValue::Schema { schema, .. } => v.visit_schema_object(schema),
+        ParameterValue::Content { content } => visit_content(v, content),
+    }
+}
+
+fn visit_request_body<V: Visitor + ?Sized>(v: &mut V, request_body: &mut RequestBody) {
+    visit_content(v, &mut request_body.content);
+}
+
+pub(crate) fn visit_response<V: Visitor + ?Sized>(v: &mut V, response: &mut Response) {
+    visit_content(v, &mut response.content);
+    for header in response.headers.values_mut() {
+        if let RefOr::Object(header) = header {
+            match &mut header.value {
+                ParameterValue::Schema { schema, .. } => v.visit_schema_object(schema),
+                ParameterValue::Content { content } => visit_content(v, content),
+            }
+        }
+    }
+}
+
+fn visit_content<V: Visitor + ?Sized>(v: &mut V, content: &mut Map<String, MediaType>) {
+    for media_type in content.values_mut() {
+        if let Some(schema) = &mut media_type.schema {
+            v.visit_schema_object(schema);
+        }
+    }
+}
+
+/// Custom post-processing of the generated schemas, added using
+/// [`OpenApiSettings::schema_visitors`](crate::settings::OpenApiSettings::schema_visitors).
+/// `visit` is called for every schema object in the document, including the schemas nested in
+/// other schemas, after the document is generated.
+pub trait SchemaVisitor: Send + Sync {
+    /// Change the schema.
+    fn visit(&self, schema: &mut SchemaObject);
+}
+
+/// Calls a [`SchemaVisitor`] for every schema object, so it can be used with `visit_openapi`.
+pub struct ApplySchemaVisitor<'a>(pub &'a dyn SchemaVisitor);
+
+impl Visitor for ApplySchemaVisitor<'_> {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        self.0.visit(schema);
+        visit_schema_object(self, schema);
+    }
+}
+
+/// Replaces the OpenAPI 3.0 `nullable: true` keyword with a `null` type, as used by
+/// OpenAPI 3.1 and JSON Schema. For example `{"type": "string", "nullable": true}`
+/// becomes `{"type": ["string", "null"]}`.
+#[derive(Debug, Clone, Default)]
+pub struct ReplaceNullableWithNullType;
+
+impl Visitor for ReplaceNullableWithNullType {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        let nullable = schema.extensions.remove("nullable");
+        if nullable.and_then(|n| n.as_bool()) == Some(true) {
+            match schema.instance_type.take() {
+                Some(SingleOrVec::Single(instance_type)) => {
+                    schema.instance_type =
+                        Some(SingleOrVec::Vec(vec![*instance_type, InstanceType::Null]));
+                }
+                Some(SingleOrVec::Vec(mut instance_types)) => {
+                    if !instance_types.contains(&InstanceType::Null) {
+                        instance_types.push(InstanceType::Null);
+                    }
+                    schema.instance_type = Some(SingleOrVec::Vec(instance_types));
+                }
+                None => {
+                    // No type to extend (for example a `$ref`), so allow `null` as an alternative.
+                    let inner = std::mem::take(schema);
+                    schema.subschemas().any_of = Some(vec![
+                        Schema::Object(inner),
+                        Schema::Object(SchemaObject {
+                            instance_type: Some(InstanceType::Null.into()),
+                            ..SchemaObject::default()
+                        }),
+                    ]);
+                }
+            }
+        }
+        visit_schema_object(self, schema);
+    }
+}
+
+/// Replaces a `null` type with the OpenAPI 3.0 `nullable: true` keyword, the opposite of
+/// `ReplaceNullableWithNullType`. For example `{"anyOf": [{"$ref": "..."}, {"type": "null"}]}`
+/// becomes `{"allOf": [{"$ref": "..."}], "nullable": true}` and `{"type": ["string", "null"]}`
+/// becomes `{"type": "string", "nullable": true}`.
+#[derive(Debug, Clone, Default)]
+pub struct ReplaceNullTypeWithNullable;
+
+impl Visitor for ReplaceNullTypeWithNullable {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        if let Some(SingleOrVec::Vec(instance_types)) = &mut schema.instance_type {
+            if instance_types.len() > 1 && instance_types.contains(&InstanceType::Null) {
+                instance_types.retain(|instance_type| *instance_type != InstanceType::Null);
+                if instance_types.len() == 1 {
+                    schema.instance_type = Some(SingleOrVec::Single(Box::new(instance_types[0])));
+                }
+                set_nullable(schema);
+            }
+        }
+        if let Some(mut inner) = take_non_null_any_of(schema) {
+            if inner.reference.is_some() {
+                // Keywords next to a `$ref` are ignored in OpenAPI 3.0, so wrap it in an `allOf`.
+                inner = SchemaObject {
+                    subschemas: Some(Box::new(SubschemaValidation {
+                        all_of: Some(vec![Schema::Object(inner)]),
+                        ..SubschemaValidation::default()
+                    })),
+                    ..SchemaObject::default()
+                };
+            }
+            // Keep the `description`, `title`, etc. of the outer schema.
+            let outer = std::mem::replace(schema, inner);
+            if schema.metadata.is_none() {
+                schema.metadata = outer.metadata;
+            }
+            schema.extensions.extend(outer.extensions);
+            set_nullable(schema);
+        }
+        visit_schema_object(self, schema);
+    }
+}
+
+fn set_nullable(schema: &mut SchemaObject) {
+    schema
+        .extensions
+        .insert("nullable".to_owned(), serde_json::Value::Bool(true));
+}
+
+/// Take the non-null schema out of an `anyOf: [T, {type: null}]` (in any order), if the schema
+/// has nothing else besides metadata and extensions.
+fn take_non_null_any_of(schema: &mut SchemaObject) -> Option<SchemaObject> {
+    let is_null = |schema: &Schema| match schema {
+        Schema::Object(schema) => {
+            schema.instance_type == Some(SingleOrVec::Single(Box::new(InstanceType::Null)))
+                && *schema
+                    == SchemaObject {
+                        instance_type: schema.instance_type.clone(),
+                        ..SchemaObject::default()
+                    }
+        }
+        Schema::Bool(_) => false,
+    };
+    let only_any_of = schema.instance_type.is_none()
+        && schema.format.is_none()
+        && schema.enum_values.is_none()
+        && schema.const_value.is_none()
+        && schema.number.is_none()
+        && schema.string.is_none()
+        && schema.array.is_none()
+        && schema.object.is_none()
+        && schema.reference.is_none();
+    let subschemas = schema.subschemas.as_mut()?;
+    let any_of = subschemas.any_of.as_ref()?;
+    let null_index = any_of.iter().position(is_null)?;
+    if !only_any_of
+        || any_of.len() != 2
+        || !matches!(any_of[1 - null_index], Schema::Object(_))
+        || **subschemas
+            != (SubschemaValidation {
+                any_of: subschemas.any_of.clone(),
+                ..SubschemaValidation::default()
+            })
+    {
+        return None;
+    }
+    let mut any_of = subschemas.any_of.take()?;
+    schema.subschemas = None;
+    match any_of.swap_remove(1 - null_index) {
+        Schema::Object(inner) => Some(inner),
+        Schema::Bool(_) => None,
+    }
+}
+
+/// Moves a `$ref` that has other keywords next to it, like a `description`, into an `allOf`,
+/// because OpenAPI 3.0 ignores everything next to a `$ref`. For example
+/// `{"$ref": "...", "description": "..."}` becomes
+/// `{"allOf": [{"$ref": "..."}], "description": "..."}`.
+#[derive(Debug, Clone, Default)]
+pub struct WrapRefWithSiblings;
+
+impl Visitor for WrapRefWithSiblings {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        if schema.is_ref() {
+            let has_siblings = *schema
+                != SchemaObject {
+                    reference: schema.reference.clone(),
+                    ..SchemaObject::default()
+                };
+            if has_siblings {
+                let reference = Schema::new_ref(schema.reference.take().unwrap_or_default());
+                schema
+                    .subschemas()
+                    .all_of
+                    .get_or_insert_with(Vec::new)
+                    .insert(0, reference);
+            }
+        }
+        visit_schema_object(self, schema);
+    }
+}
+
+/// Replaces an `allOf` with a single `$ref` by the `$ref` itself, when the schema only has
+/// metadata and extensions next to it. OpenAPI 3.1 allows keywords next to a `$ref`, so
+/// `{"allOf": [{"$ref": "..."}], "description": "..."}` becomes
+/// `{"$ref": "...", "description": "..."}`. The opposite of `WrapRefWithSiblings`.
+#[derive(Debug, Clone, Default)]
+pub struct UnwrapRefWithSiblings;
+
+impl Visitor for UnwrapRefWithSiblings {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        let reference = match &schema.subschemas {
+            Some(subschemas) => match subschemas.all_of.as_deref() {
+                Some([Schema::Object(inner)])
+                    if inner.is_ref()
+                        && **subschemas
+                            == (SubschemaValidation {
+                                all_of: subschemas.all_of.clone(),
+                                ..SubschemaValidation::default()
+                            }) =>
+                {
+                    inner.reference.clone()
+                }
+                _ => None,
+            },
+            None => None,
+        };
+        let only_metadata = *schema
+            == SchemaObject {
+                metadata: schema.metadata.clone(),
+                subschemas: schema.subschemas.clone(),
+                extensions: schema.extensions.clone(),
+                ..SchemaObject::default()
+            };
+        if let (Some(reference), true) = (reference, only_metadata) {
+            schema.subschemas = None;
+            schema.reference = Some(reference);
+        }
+        visit_schema_object(self, schema);
+    }
+}
+
+/// Replaces the `$ref` of schemas using the given map of old to new references.
+/// For example `#/components/schemas/Old` to `#/components/schemas/New`.
+#[derive(Debug, Clone, Default)]
+pub struct ReplaceReferences {
+    /// Map of old references to the references that replace them.
+    pub references: Map<String, String>,
+}
+
+impl Visitor for ReplaceReferences {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        if let Some(new_reference) = schema
+            .reference
+            .as_ref()
+            .and_then(|reference| self.references.get(reference))
+        {
+            schema.reference = Some(new_reference.clone());
+        }
+        visit_schema_object(self, schema);
+    }
+}
+
+/// Replaces the `$ref` of schemas with the schema they point to, using the given definitions.
+/// References to recursive schemas are kept as `$ref` once the schema is already inlined
+/// further up, so a recursive type does not loop forever.
+#[derive(Debug, Clone)]
+pub struct InlineReferences<'a> {
+    /// The definitions that can be referenced, like `SchemaGenerator::definitions`.
+    pub definitions: &'a Map<String, Schema>,
+    /// The prefix of the references, like `SchemaSettings::definitions_path`.
+    pub prefix: String,
+    /// Names of the schemas that are currently being inlined.
+    stack: Vec<String>,
+}
+
+impl<'a> InlineReferences<'a> {
+    /// Create a new `InlineReferences` visitor.
+    #[must_use]
+    pub fn new(definitions: &'a Map<String, Schema>, prefix: String) -> Self {
+        InlineReferences {
+            definitions,
+            prefix,
+            stack: Vec::new(),
+        }
+    }
+}
+
+impl Visitor for InlineReferences<'_> {
+    fn visit_schema_object(&mut self, schema: &mut SchemaObject) {
+        if let Some(reference) = &schema.reference {
+            let definition = reference
+                .strip_prefix(&self.prefix)
+                .filter(|name| !self.stack.iter().any(|item| item == name))
+                .and_then(|name| match self.definitions.get(name) {
+                    Some(Schema::Object(definition)) => Some((name.to_owned(), definition.clone())),
+                    _ => None,
+                });
+            // Unknown or recursive references are kept.
+            if let Some((name, mut definition)) = definition {
+                self.stack.push(name);
+                self.visit_schema_object(&mut definition);
+                self.stack.pop();
+                *schema = definition;
+            }
+            return;
+        }
+        visit_schema_object(self, schema);
+    }
+}
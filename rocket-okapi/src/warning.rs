@@ -0,0 +1,110 @@
+use crate::OpenApiError;
+use rocket::http::Method;
+use std::fmt;
+
+/// A problem found while generating the `OpenApi` document that did not stop the generation.
+/// The warnings are also logged when they are created.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum OpenApiWarning {
+    /// An operation for the `CONNECT` HTTP method was not added,
+    /// see `OpenApiSettings::connect_handling`.
+    DroppedConnect {
+        /// The path of the dropped operation.
+        path: String,
+    },
+    /// `OpenApiGenerator::add_operation` could not add the operation.
+    OperationError(OpenApiError),
+    /// An added operation has a problem, like a path parameter that is not in the path.
+    InvalidOperation(OpenApiError),
+    /// A server added with `OpenApiGenerator::add_server` has an invalid variable.
+    InvalidServer(OpenApiError),
+    /// An example does not match the schema of its content.
+    InvalidExample {
+        /// The path of the operation.
+        path: String,
+        /// The HTTP method of the operation.
+        method: Method,
+        /// The content type of the example.
+        content_type: String,
+    },
+    /// The `info` of the document has no `title` or `version`.
+    EmptyInfo,
+    /// A schema was not added to the definitions because a different schema with the same name
+    /// is already there, like in `OpenApiGenerator::add_string_enum_schema`.
+    DuplicateSchemaName {
+        /// The name of the schema.
+        name: String,
+    },
+    /// A constraint set with `OpenApiGenerator::set_property_constraints` does not apply to the
+    /// type of the property, like a `minimum` on a string.
+    InvalidConstraint {
+        /// The name of the schema.
+        schema_name: String,
+        /// The name of the property.
+        property: String,
+        /// The keywords of the constraint.
+        constraint: String,
+    },
+    /// A security requirement set with `OpenApiGenerator::set_global_security` uses a scheme
+    /// that was not added to the `securitySchemes`.
+    UnknownSecurityScheme {
+        /// The name of the security scheme.
+        name: String,
+    },
+    /// A `$ref` in the document points to something that does not exist.
+    UnresolvedRef {
+        /// The reference that could not be resolved.
+        reference: String,
+    },
+}
+
+impl fmt::Display for OpenApiWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DroppedConnect { path } => write!(
+                f,
+                "The operation for `CONNECT {}` is not added, OpenAPI does not support `CONNECT`.",
+                path
+            ),
+            Self::OperationError(err) => write!(f, "The operation is not added: {}", err),
+            Self::InvalidOperation(err) => write!(f, "{}", err),
+            Self::InvalidServer(err) => write!(f, "{}", err),
+            Self::InvalidExample {
+                path,
+                method,
+                content_type,
+            } => write!(
+                f,
+                "The example for `{}` of `{} {}` does not match its schema.",
+                content_type, method, path
+            ),
+            Self::EmptyInfo => write!(
+                f,
+                "The `info` of the OpenAPI document has no `title` or `version`."
+            ),
+            Self::DuplicateSchemaName { name } => write!(
+                f,
+                "A different schema with the name `{}` already exists, the new schema is not documented.",
+                name
+            ),
+            Self::InvalidConstraint {
+                schema_name,
+                property,
+                constraint,
+            } => write!(
+                f,
+                "The `{}` constraint does not apply to the type of the property `{}` of `{}`.",
+                constraint, property, schema_name
+            ),
+            Self::UnknownSecurityScheme { name } => write!(
+                f,
+                "The security scheme `{}` is not in the `securitySchemes` of the components.",
+                name
+            ),
+            Self::UnresolvedRef { reference } => {
+                write!(f, "The reference `{}` can not be resolved.", reference)
+            }
+        }
+    }
+}